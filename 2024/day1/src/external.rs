@@ -0,0 +1,205 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fs::File,
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Seek, Write},
+    iter, mem,
+};
+
+use anyhow::Result;
+
+use crate::sort::sort;
+
+type Values = Box<dyn Iterator<Item = io::Result<u64>>>;
+
+/// Most runs merged at once, which keeps the number of open files well within
+/// the usual limits
+const MAX_FAN_IN: usize = 64;
+
+/// Values collected into sorted runs of at most `run_len`, each spilled to a
+/// temporary file once full
+struct Runs {
+    run_len: usize,
+    buf: Vec<u64>,
+    /// Spilled runs by how many merges made them. Once a level has
+    /// `MAX_FAN_IN` runs they're merged into one run on the next level.
+    levels: Vec<Vec<File>>,
+}
+
+/// Write sorted values to a new run file, ready to be read back
+fn write_run(values: impl Iterator<Item = io::Result<u64>>) -> io::Result<File> {
+    let mut file = tempfile::tempfile()?;
+    let mut writer = BufWriter::new(&mut file);
+    for v in values {
+        writer.write_all(&v?.to_le_bytes())?;
+    }
+    writer.flush()?;
+    drop(writer);
+
+    file.rewind()?;
+    Ok(file)
+}
+
+fn merge_files(files: Vec<File>) -> io::Result<File> {
+    write_run(Merge::new(files.into_iter().map(read_run).collect())?)
+}
+
+fn read_run(file: File) -> Values {
+    let mut reader = BufReader::new(file);
+    Box::new(iter::from_fn(move || {
+        let mut bytes = [0; 8];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => Some(Ok(u64::from_le_bytes(bytes))),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => None,
+            Err(e) => Some(Err(e)),
+        }
+    }))
+}
+
+impl Runs {
+    fn new(run_len: usize) -> Self {
+        Self {
+            run_len,
+            buf: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, v: u64) -> io::Result<()> {
+        self.buf.push(v);
+        if self.buf.len() >= self.run_len {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        sort(&mut self.buf);
+        let mut file = write_run(self.buf.drain(..).map(Ok))?;
+
+        for level in 0.. {
+            if level == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            self.levels[level].push(file);
+            if self.levels[level].len() < MAX_FAN_IN {
+                break;
+            }
+            file = merge_files(mem::take(&mut self.levels[level]))?;
+        }
+        Ok(())
+    }
+
+    /// All the values in sorted order, merging the runs
+    fn into_sorted(mut self) -> io::Result<Merge> {
+        let mut files = self.levels.into_iter().flatten().collect::<Vec<_>>();
+        // merge the smallest runs first, leaving room for the last run
+        while files.len() >= MAX_FAN_IN {
+            let batch = files.drain(..MAX_FAN_IN).collect();
+            files.push(merge_files(batch)?);
+        }
+
+        // the last, partial run never needs to leave memory
+        sort(&mut self.buf);
+        let last: Values = Box::new(mem::take(&mut self.buf).into_iter().map(Ok));
+        let runs = files.into_iter().map(read_run);
+        Merge::new(runs.chain([last]).collect())
+    }
+}
+
+/// k-way merge of sorted runs
+struct Merge {
+    runs: Vec<Values>,
+    /// The next value of each run which has one left, with the run's index
+    heads: BinaryHeap<Reverse<(u64, usize)>>,
+}
+
+impl Merge {
+    fn new(mut runs: Vec<Values>) -> io::Result<Self> {
+        let mut heads = BinaryHeap::new();
+        for (i, run) in runs.iter_mut().enumerate() {
+            if let Some(v) = run.next() {
+                heads.push(Reverse((v?, i)));
+            }
+        }
+        Ok(Self { runs, heads })
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((v, i)) = self.heads.pop()?;
+        match self.runs[i].next() {
+            Some(Ok(next)) => self.heads.push(Reverse((next, i))),
+            Some(Err(e)) => return Some(Err(e)),
+            None => {}
+        }
+        Some(Ok(v))
+    }
+}
+
+/// Like [`crate::metrics::total_distance`], holding at most `run_len` values of
+/// each list in memory at once
+pub fn total_distance(
+    pairs: impl Iterator<Item = Result<(u64, u64)>>,
+    run_len: usize,
+) -> Result<u64> {
+    let mut left = Runs::new(run_len);
+    let mut right = Runs::new(run_len);
+    for pair in pairs {
+        let (l, r) = pair?;
+        left.push(l)?;
+        right.push(r)?;
+    }
+
+    Ok(left
+        .into_sorted()?
+        .zip(right.into_sorted()?)
+        .map(|(l, r)| Ok(l?.abs_diff(r?)))
+        .sum::<io::Result<u64>>()?)
+}
+
+/// Like [`crate::metrics::similarity_score`] in a single pass, holding only the
+/// frequency of each distinct value in memory
+pub fn similarity_score(pairs: impl Iterator<Item = Result<(u64, u64)>>) -> Result<u64> {
+    let mut left_freqs: HashMap<u64, u64> = HashMap::new();
+    let mut right_freqs: HashMap<u64, u64> = HashMap::new();
+    for pair in pairs {
+        let (l, r) = pair?;
+        *left_freqs.entry(l).or_default() += 1;
+        *right_freqs.entry(r).or_default() += 1;
+    }
+
+    Ok(left_freqs
+        .into_iter()
+        .map(|(v, lc)| v * lc * right_freqs.get(&v).copied().unwrap_or(0))
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics;
+
+    #[test]
+    fn total_distance_matches_in_memory() {
+        // enough runs of 3 values for merges on two levels
+        let n = 3 * MAX_FAN_IN * MAX_FAN_IN + 100;
+        let mut state = 1u64;
+        let mut random = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            (state >> 33) % 100_000
+        };
+        let (l, r): (Vec<_>, Vec<_>) = (0..n).map(|_| (random(), random())).unzip();
+
+        let pairs = l.iter().zip(&r).map(|(&l, &r)| Ok((l, r)));
+        assert_eq!(
+            total_distance(pairs, 3).unwrap(),
+            metrics::total_distance(&l, &r)
+        );
+    }
+}
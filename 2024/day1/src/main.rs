@@ -0,0 +1,146 @@
+mod external;
+mod metrics;
+mod sort;
+
+use anyhow::{bail, Result};
+use aoc_common::CommonArgs;
+use clap::{Parser, Subcommand};
+use metrics::Metric;
+use std::{
+    io::{stdin, BufRead},
+    num::ParseIntError,
+};
+use thiserror::Error;
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Compare the lists with each metric, printing one per line
+    Metrics {
+        #[arg(required = true)]
+        metrics: Vec<Metric>,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Column (counting from 1) holding the left list
+    #[arg(long, global = true, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    left: u32,
+    /// Column (counting from 1) holding the right list
+    #[arg(long, global = true, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..))]
+    right: u32,
+
+    /// Stream the input, spilling sorted runs to temporary files rather than
+    /// holding the lists in memory (p1 and p2 only)
+    #[arg(long, global = true)]
+    stream: bool,
+    /// Most values of each list to hold in memory at once when streaming
+    #[arg(long, global = true, default_value_t = 1 << 22, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    run_len: usize,
+}
+
+#[derive(Debug, Error)]
+enum Error {
+    #[error("Line {line}: expected at least {needed} columns, found {found}")]
+    MissingColumn {
+        line: usize,
+        needed: usize,
+        found: usize,
+    },
+    #[error("Line {line}: invalid number {value:?}")]
+    InvalidNumber {
+        line: usize,
+        value: String,
+        source: ParseIntError,
+    },
+}
+
+/// Columns may be separated by any mix of whitespace and commas
+fn split_columns(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|col| !col.is_empty())
+}
+
+/// Read pairs of values from the `left` and `right` columns (counting from 0),
+/// skipping blank lines and `#` comments
+fn parse_pairs(
+    inp: impl BufRead,
+    left: usize,
+    right: usize,
+) -> impl Iterator<Item = Result<(u64, u64)>> {
+    inp.lines().enumerate().filter_map(move |(idx, line)| {
+        let s = match line {
+            Ok(s) => s,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let s = s.trim();
+        if s.is_empty() || s.starts_with('#') {
+            return None;
+        }
+
+        let line = idx + 1;
+        let cols = split_columns(s).collect::<Vec<_>>();
+        let parse = |col: usize| -> Result<u64, Error> {
+            let value = cols.get(col).ok_or(Error::MissingColumn {
+                line,
+                needed: left.max(right) + 1,
+                found: cols.len(),
+            })?;
+            value.parse().map_err(|source| Error::InvalidNumber {
+                line,
+                value: (*value).to_string(),
+                source,
+            })
+        };
+
+        Some(
+            parse(left)
+                .and_then(|l| Ok((l, parse(right)?)))
+                .map_err(Into::into),
+        )
+    })
+}
+
+fn parse_lists(inp: impl BufRead, left: usize, right: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+    parse_pairs(inp, left, right).collect()
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let (left, right) = (args.left as usize - 1, args.right as usize - 1);
+
+    if args.stream {
+        let _solve = info_span!("solve", part = ?args.part).entered();
+        let pairs = parse_pairs(stdin().lock(), left, right);
+        match args.part {
+            Part::P1 => println!("{}", external::total_distance(pairs, args.run_len)?),
+            Part::P2 => println!("{}", external::similarity_score(pairs)?),
+            Part::Metrics { .. } => bail!("Metrics need the lists in memory, so can't stream"),
+        }
+        return Ok(());
+    }
+
+    let (l, r) = info_span!("parse").in_scope(|| parse_lists(stdin().lock(), left, right))?;
+
+    let _solve = info_span!("solve", part = ?args.part).entered();
+    match args.part {
+        Part::P1 => println!("{}", metrics::total_distance(&l, &r)),
+        Part::P2 => println!("{}", metrics::similarity_score(&l, &r)),
+        Part::Metrics { metrics } => {
+            for metric in metrics {
+                println!("{}", metric.compute(&l, &r)?);
+            }
+        }
+    }
+
+    Ok(())
+}
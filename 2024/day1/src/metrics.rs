@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use anyhow::{ensure, Result};
+use clap::ValueEnum;
+
+use crate::sort::sort;
+
+/// Ways of comparing the left and right lists
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Metric {
+    /// Sum of the differences between sorted pairs (part 1)
+    L1,
+    /// Euclidean distance between the sorted lists
+    L2,
+    /// Largest difference between sorted pairs
+    Max,
+    /// Number of values in both lists, counting repeats
+    Intersection,
+    /// Multiset intersection size over multiset union size
+    Jaccard,
+    /// Sum of the values in both lists, counting repeats
+    WeightedOverlap,
+    /// Sum of each left value times how often it appears in the right list (part 2)
+    Similarity,
+    /// Spearman rank correlation between the lists, row by row
+    Spearman,
+}
+
+pub enum Value {
+    Int(u64),
+    Float(f64),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(i) => write!(f, "{i}"),
+            // adding zero turns -0, e.g. from summing nothing, into 0
+            Self::Float(x) => write!(f, "{}", x + 0.0),
+        }
+    }
+}
+
+impl Metric {
+    pub fn compute(self, l: &[u64], r: &[u64]) -> Result<Value> {
+        Ok(match self {
+            Self::L1 => Value::Int(total_distance(l, r)),
+            Self::L2 => Value::Float(l2_distance(l, r)),
+            Self::Max => Value::Int(max_distance(l, r)),
+            Self::Intersection => Value::Int(intersection_size(l, r)),
+            Self::Jaccard => Value::Float(jaccard(l, r)),
+            Self::WeightedOverlap => Value::Int(weighted_overlap(l, r)),
+            Self::Similarity => Value::Int(similarity_score(l, r)),
+            Self::Spearman => Value::Float(spearman(l, r)?),
+        })
+    }
+}
+
+fn sorted(v: &[u64]) -> Vec<u64> {
+    let mut v = v.to_vec();
+    sort(&mut v);
+    v
+}
+
+/// Differences between the smallest values in each list, then the second smallest, etc.
+fn sorted_diffs(l: &[u64], r: &[u64]) -> impl Iterator<Item = u64> {
+    sorted(l)
+        .into_iter()
+        .zip(sorted(r))
+        .map(|(l, r)| l.abs_diff(r))
+}
+
+pub fn total_distance(l: &[u64], r: &[u64]) -> u64 {
+    sorted_diffs(l, r).sum()
+}
+
+fn l2_distance(l: &[u64], r: &[u64]) -> f64 {
+    sorted_diffs(l, r)
+        .map(|d| (d as f64).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn max_distance(l: &[u64], r: &[u64]) -> u64 {
+    sorted_diffs(l, r).max().unwrap_or(0)
+}
+
+fn freq_map(v: &[u64]) -> HashMap<u64, u64> {
+    let mut res = HashMap::new();
+    for &elem in v {
+        *res.entry(elem).or_default() += 1;
+    }
+
+    res
+}
+
+/// Each value in both lists, with the number of times it appears in both
+fn common_counts(l: &[u64], r: &[u64]) -> impl Iterator<Item = (u64, u64)> {
+    let right_freqs = freq_map(r);
+    freq_map(l)
+        .into_iter()
+        .filter_map(move |(v, lc)| Some((v, lc.min(*right_freqs.get(&v)?))))
+}
+
+fn intersection_size(l: &[u64], r: &[u64]) -> u64 {
+    common_counts(l, r).map(|(_, count)| count).sum()
+}
+
+fn jaccard(l: &[u64], r: &[u64]) -> f64 {
+    let intersection = intersection_size(l, r);
+    let union = (l.len() + r.len()) as u64 - intersection;
+    if union == 0 {
+        // two empty lists are identical
+        return 1.0;
+    }
+
+    intersection as f64 / union as f64
+}
+
+fn weighted_overlap(l: &[u64], r: &[u64]) -> u64 {
+    common_counts(l, r).map(|(v, count)| v * count).sum()
+}
+
+pub fn similarity_score(l: &[u64], r: &[u64]) -> u64 {
+    let right_freqs = freq_map(r);
+
+    l.iter()
+        .map(|&v| v * right_freqs.get(&v).copied().unwrap_or(0))
+        .sum()
+}
+
+/// Rank of each value in `v`, with tied values sharing the mean of their ranks
+fn ranks(v: &[u64]) -> Vec<f64> {
+    let mut order = (0..v.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| v[i]);
+
+    let mut res = vec![0.0; v.len()];
+    let mut start = 0;
+    for tied in order.chunk_by(|&i, &j| v[i] == v[j]) {
+        // ranks start + 1 ..= start + len, averaged
+        let rank = start as f64 + (tied.len() + 1) as f64 / 2.0;
+        for &i in tied {
+            res[i] = rank;
+        }
+        start += tied.len();
+    }
+
+    res
+}
+
+fn spearman(l: &[u64], r: &[u64]) -> Result<f64> {
+    let (lr, rr) = (ranks(l), ranks(r));
+    let n = lr.len() as f64;
+    let mean = (n + 1.0) / 2.0;
+
+    let (mut cov, mut lvar, mut rvar) = (0.0, 0.0, 0.0);
+    for (l, r) in lr.into_iter().zip(rr) {
+        cov += (l - mean) * (r - mean);
+        lvar += (l - mean).powi(2);
+        rvar += (r - mean).powi(2);
+    }
+
+    // ranks only vary if the values do
+    ensure!(
+        lvar > 0.0 && rvar > 0.0,
+        "Spearman correlation is undefined unless both lists have at least two distinct values"
+    );
+
+    Ok(cov / (lvar * rvar).sqrt())
+}
@@ -0,0 +1,66 @@
+/// Slices at most this long are comparison sorted
+const SMALL_LEN: usize = 64;
+/// Values spanning at most this many times the slice's length are counting sorted
+const COUNTING_SORT_RANGE_PER_VALUE: u64 = 4;
+
+const DIGIT_BITS: u32 = 16;
+
+/// Sort in linear time where that's faster: counting sort if the values span a
+/// small range for their number, LSD radix sort if there are more values than
+/// digit buckets, or a comparison sort otherwise
+pub fn sort(v: &mut [u64]) {
+    if v.len() <= SMALL_LEN {
+        v.sort_unstable();
+        return;
+    }
+    let (Some(&min), Some(&max)) = (v.iter().min(), v.iter().max()) else {
+        return;
+    };
+
+    if max - min < COUNTING_SORT_RANGE_PER_VALUE * v.len() as u64 {
+        counting_sort(v, min, max);
+    } else if v.len() >= 1 << DIGIT_BITS {
+        radix_sort(v, min, max);
+    } else {
+        // each radix pass walks every bucket, which would outweigh sorting so few values
+        v.sort_unstable();
+    }
+}
+
+fn counting_sort(v: &mut [u64], min: u64, max: u64) {
+    let mut counts = vec![0; (max - min) as usize + 1];
+    for &x in v.iter() {
+        counts[(x - min) as usize] += 1;
+    }
+
+    let mut i = 0;
+    for (offset, count) in counts.into_iter().enumerate() {
+        v[i..i + count].fill(min + offset as u64);
+        i += count;
+    }
+}
+
+fn radix_sort(v: &mut [u64], min: u64, max: u64) {
+    // sort offsets from the minimum, skipping digits which are zero in all of them
+    let bits = u64::BITS - (max - min).leading_zeros();
+    let mut buf = vec![0; v.len()];
+    let mut starts = vec![0; 1 << DIGIT_BITS];
+    for shift in (0..bits).step_by(DIGIT_BITS as usize) {
+        let digit = |x: u64| ((x - min) >> shift) as usize & ((1 << DIGIT_BITS) - 1);
+
+        starts.fill(0);
+        for &x in v.iter() {
+            starts[digit(x)] += 1;
+        }
+        let mut total = 0;
+        for start in &mut starts {
+            (*start, total) = (total, total + *start);
+        }
+
+        for &x in v.iter() {
+            buf[starts[digit(x)]] = x;
+            starts[digit(x)] += 1;
+        }
+        v.copy_from_slice(&buf);
+    }
+}
@@ -6,7 +6,9 @@ use std::{
 };
 
 use anyhow::{anyhow, ensure, Result};
+use aoc_common::{memo::Memo, CommonArgs};
 use clap::{Parser, Subcommand};
+use tracing::{info, info_span};
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -18,6 +20,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 struct Map {
@@ -99,7 +103,7 @@ fn parse_map(inp: impl BufRead) -> Result<Map> {
     })
 }
 
-type MemoTable = HashMap<Idx, Rc<HashMap<Idx, usize>>>;
+type MemoTable = Memo<Idx, Rc<HashMap<Idx, usize>>>;
 
 fn calculate_reachable(memo: &mut MemoTable, map: &Map, idx: Idx) -> Rc<HashMap<Idx, usize>> {
     if let Some(res) = memo.get(&idx) {
@@ -130,10 +134,12 @@ fn calculate_reachable(memo: &mut MemoTable, map: &Map, idx: Idx) -> Rc<HashMap<
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let map = parse_map(stdin().lock())?;
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let map = info_span!("parse").in_scope(|| parse_map(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
-    let mut memo = HashMap::new();
+    let mut memo = Memo::new();
     let trailheads_reachability = map
         .trailheads()
         .map(|idx| calculate_reachable(&mut memo, &map, idx));
@@ -143,6 +149,7 @@ fn main() -> Result<()> {
             .map(|m| m.values().sum::<usize>())
             .sum(),
     };
+    info!(memo = %memo.stats(), "search finished");
     println!("{total_score}");
 
     Ok(())
@@ -4,7 +4,9 @@ use std::{
 };
 
 use anyhow::{anyhow, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -16,6 +18,12 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Number of times to blink (default: 25 for p1, 75 for p2)
+    #[arg(long, global = true)]
+    blinks: Option<usize>,
 }
 
 fn parse_inp(inp: impl BufRead) -> Result<Vec<u64>> {
@@ -48,17 +56,20 @@ fn transform(stone: u64) -> Vec<u64> {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let stones = info_span!("parse").in_scope(|| parse_inp(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let mut stone_counts: HashMap<u64, usize> = HashMap::new();
-    for stone in parse_inp(stdin().lock())? {
+    for stone in stones {
         *stone_counts.entry(stone).or_default() += 1;
     }
 
-    let num_iters = match args.part {
+    let num_iters = args.blinks.unwrap_or(match args.part {
         Part::P1 => 25,
         Part::P2 => 75,
-    };
+    });
 
     for _ in 0..num_iters {
         let mut new_counts = HashMap::new();
@@ -6,7 +6,9 @@ use std::{
 };
 
 use anyhow::{anyhow, ensure, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -18,6 +20,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type Plant = char;
@@ -197,8 +201,10 @@ fn count_sides(map: &Map, edges: &HashSet<Edge>) -> usize {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let map = Map::parse(stdin().lock())?;
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let map = info_span!("parse").in_scope(|| Map::parse(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let mut seen = HashSet::new();
 
@@ -213,7 +219,7 @@ fn main() -> Result<()> {
                 };
                 total += price;
 
-                seen.extend(contained.into_iter());
+                seen.extend(contained);
             }
         }
     }
@@ -1,18 +1,16 @@
 use std::io::{read_to_string, stdin, BufRead};
 
 use anyhow::Result;
+use aoc_common::{parsers::num, CommonArgs};
 use clap::{Parser, Subcommand};
 use nom::{
     bytes::complete::tag,
-    character::{
-        complete::{digit1, line_ending},
-        streaming::one_of,
-    },
-    combinator::map_res,
+    character::{complete::line_ending, streaming::one_of},
     error::Error,
     multi::separated_list1,
     Err, IResult,
 };
+use tracing::info_span;
 use z3::{
     ast::{Ast, Int},
     Config, Context, Optimize, SatResult,
@@ -28,6 +26,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 #[derive(Debug)]
@@ -43,10 +43,6 @@ struct ClawMachine {
     prize: Vec2,
 }
 
-fn num(s: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse)(s)
-}
-
 fn button_line(s: &str) -> IResult<&str, Vec2> {
     let (s, _) = tag("Button ")(s)?;
     let (s, _) = one_of("AB")(s)?;
@@ -135,9 +131,11 @@ fn min_tokens(machine: &ClawMachine, offset: u64) -> Option<u64> {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
 
-    let machines = claw_machines(stdin().lock())?;
+    let machines = info_span!("parse").in_scope(|| claw_machines(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let offset = match args.part {
         Part::P1 => 0,
@@ -0,0 +1,239 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::stdin,
+};
+
+use anyhow::Result;
+use aoc_common::{animation, parsers::num, CommonArgs, Session};
+use clap::{Parser, Subcommand};
+use nom::{
+    character::{
+        complete::{char, space1},
+        streaming::one_of,
+    },
+    error::Error,
+    sequence::separated_pair,
+    Err, IResult,
+};
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Check the input against the assumptions the solvers make
+    Lint,
+    /// Print the robots after each second
+    Animate {
+        /// First second to show (default: shortly before the tree appears)
+        #[arg(long)]
+        from: Option<usize>,
+        /// Number of seconds to show
+        #[arg(long, default_value_t = 20)]
+        frames: usize,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Width of the space the robots move in
+    #[arg(long, global = true, default_value_t = 101)]
+    width: usize,
+    /// Height of the space the robots move in
+    #[arg(long, global = true, default_value_t = 103)]
+    height: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Vec2 {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Debug)]
+struct Robot {
+    pos: Vec2,
+    vel: Vec2,
+}
+
+fn vec2(s: &str) -> IResult<&str, Vec2> {
+    let (s, (x, y)) = separated_pair(num, char(','), num)(s)?;
+    Ok((s, Vec2 { x, y }))
+}
+
+fn component(s: &str) -> IResult<&str, Vec2> {
+    let (s, (_, vec)) = separated_pair(one_of("pv"), char('='), vec2)(s)?;
+    Ok((s, vec))
+}
+
+fn robot(s: &str) -> IResult<&str, Robot> {
+    let (s, (pos, vel)) = separated_pair(component, space1, component)(s)?;
+
+    Ok((s, Robot { pos, vel }))
+}
+
+fn determine_pos(robot: &Robot, width: usize, height: usize, steps: usize) -> Vec2 {
+    let x = (robot.pos.x + i64::try_from(steps).unwrap() * robot.vel.x)
+        .rem_euclid(i64::try_from(width).unwrap());
+    let y = (robot.pos.y + i64::try_from(steps).unwrap() * robot.vel.y)
+        .rem_euclid(i64::try_from(height).unwrap());
+
+    Vec2 { x, y }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum Half {
+    Lower,
+    Upper,
+}
+
+type Quadrant = (Half, Half);
+
+fn half(pos: i64, len: usize) -> Option<Half> {
+    let threshold = i64::try_from(len).unwrap() / 2;
+    if len % 2 == 1 && pos == threshold {
+        None
+    } else if pos < threshold {
+        Some(Half::Lower)
+    } else {
+        Some(Half::Upper)
+    }
+}
+
+fn quadrant(pos: &Vec2, width: usize, height: usize) -> Option<Quadrant> {
+    match (half(pos.x, width), half(pos.y, height)) {
+        (Some(x), Some(y)) => Some((x, y)),
+        _ => None,
+    }
+}
+
+fn calc_safety_factor(robots: &[Robot], width: usize, height: usize, steps: usize) -> usize {
+    let mut quadrant_counts: HashMap<Quadrant, usize> = HashMap::new();
+    for robot in robots {
+        let pos = determine_pos(robot, width, height, steps);
+
+        if let Some(quadrant) = quadrant(&pos, width, height) {
+            *quadrant_counts.entry(quadrant).or_default() += 1;
+        }
+    }
+
+    quadrant_counts.values().product()
+}
+
+fn visualize_robots(robots: &[Robot], width: usize, height: usize, steps: usize) -> String {
+    let mut count_by_pos: HashSet<Vec2> = HashSet::new();
+    for robot in robots {
+        let pos = determine_pos(robot, width, height, steps);
+        count_by_pos.insert(pos);
+    }
+
+    let mut res = String::new();
+    for y in 0..height {
+        res.extend((0..width).map(|x| {
+            let y = i64::try_from(y).unwrap();
+            let x = i64::try_from(x).unwrap();
+            if count_by_pos.contains(&Vec2 { x, y }) {
+                '#'
+            } else {
+                '.'
+            }
+        }));
+        res.push('\n');
+    }
+
+    res
+}
+
+// Assume the arrangement with the lowest safety factor is the tree
+fn find_tree(robots: &[Robot], width: usize, height: usize, session: &Session) -> Result<usize> {
+    let search_ub = 10000;
+    let progress = session.progress("steps", Some(search_ub as u64));
+    let mut results = (0..search_ub)
+        .map(|steps| {
+            progress.inc(1)?;
+            Ok((calc_safety_factor(robots, width, height, steps), steps))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    results.sort_unstable();
+
+    let &(_, steps) = results.first().unwrap();
+    Ok(steps)
+}
+
+fn assumption_violations(
+    robots: &[Robot],
+    width: usize,
+    height: usize,
+    tree_steps: usize,
+) -> Vec<String> {
+    // the tree picture is drawn with every robot on its own tile
+    let positions: HashSet<Vec2> = robots
+        .iter()
+        .map(|robot| determine_pos(robot, width, height, tree_steps))
+        .collect();
+
+    if positions.len() == robots.len() {
+        Vec::new()
+    } else {
+        vec![format!(
+            "robots overlap after {tree_steps} steps, so the lowest safety factor is probably not the tree"
+        )]
+    }
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let session = args.common.init()?;
+
+    let robots = info_span!("parse").in_scope(|| {
+        stdin()
+            .lines()
+            .map(|line| -> Result<Robot> {
+                let line = line?;
+                let (_, robot) = robot(&line).map_err(Err::<Error<&str>>::to_owned)?;
+                Ok(robot)
+            })
+            .collect::<Result<Vec<Robot>>>()
+    })?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
+
+    match args.part {
+        Part::P1 => println!(
+            "{}",
+            calc_safety_factor(&robots, args.width, args.height, 100)
+        ),
+        Part::P2 | Part::Lint => {
+            let steps = find_tree(&robots, args.width, args.height, &session)?;
+            let violations = assumption_violations(&robots, args.width, args.height, steps);
+            aoc_common::check_assumptions(violations)?;
+
+            if let Part::Lint = args.part {
+                println!("All input assumptions hold");
+            } else {
+                print!(
+                    "{}",
+                    visualize_robots(&robots, args.width, args.height, steps)
+                );
+                println!("{steps}");
+            }
+        }
+        Part::Animate { from, frames } => {
+            let from = match from {
+                Some(from) => from,
+                None => find_tree(&robots, args.width, args.height, &session)?
+                    .saturating_sub(frames / 2),
+            };
+            for steps in from..from + frames {
+                let robots = visualize_robots(&robots, args.width, args.height, steps);
+                animation::print_frame(&format!("After {steps} seconds\n{robots}"));
+            }
+        }
+    }
+
+    Ok(())
+}
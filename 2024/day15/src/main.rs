@@ -4,7 +4,9 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -16,6 +18,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type Coord = (usize, usize);
@@ -131,7 +135,7 @@ fn boxes_to_move(map: &Map, coord: Coord, dir: &Direction, part: &Part) -> Optio
         let mut to_move = HashSet::from([(r, c)]);
         for dest in dests {
             if let Some(others) = boxes_to_move(map, dest, dir, part) {
-                to_move.extend(others.into_iter());
+                to_move.extend(others);
             } else {
                 return None;
             }
@@ -163,9 +167,12 @@ fn move_all_boxes(map: &mut Map, to_move: HashSet<Coord>, dir: &Direction, part:
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
 
-    let (mut map, moves) = parse_input(stdin().lock(), &args.part)?;
+    let (mut map, moves) =
+        info_span!("parse").in_scope(|| parse_input(stdin().lock(), &args.part))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
     for dir in moves {
         let robot_dest = dest(&map.robot, &dir);
         if let Some(to_move) = boxes_to_move(&map, robot_dest, &dir, &args.part) {
@@ -5,7 +5,9 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::{info, info_span, trace};
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -17,6 +19,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type Coords = (usize, usize);
@@ -103,8 +107,10 @@ const fn go_forward((r, c): Coords, dir: Direction) -> Coords {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let map = parse_map(stdin().lock())?;
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let map = info_span!("parse").in_scope(|| parse_map(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let state = (map.start, Direction::East);
     let mut to_visit = BinaryHeap::new();
@@ -112,10 +118,13 @@ fn main() -> Result<()> {
     to_visit.push(Reverse((0, state)));
     min_dist.insert(state, (0, HashSet::from([map.start])));
 
+    let mut states_expanded = 0;
+    let mut stale_pops = 0;
     let best_score = loop {
         let Some(Reverse((score, (coords, dir)))) = to_visit.pop() else {
             bail!("No path");
         };
+        trace!(score, ?coords, ?dir, "pop");
 
         if coords == map.end {
             break score;
@@ -123,8 +132,10 @@ fn main() -> Result<()> {
         let (best_seen, best_path) = min_dist.get(&(coords, dir)).unwrap();
         if *best_seen < score {
             // already checked a better path to this state
+            stale_pops += 1;
             continue;
         }
+        states_expanded += 1;
         let path = best_path.clone();
 
         let mut new_states = Vec::new();
@@ -160,6 +171,12 @@ fn main() -> Result<()> {
             to_visit.push(Reverse((score, new_state)));
         }
     };
+    info!(
+        states_expanded,
+        stale_pops,
+        states_seen = min_dist.len(),
+        "search finished"
+    );
 
     match args.part {
         Part::P1 => {
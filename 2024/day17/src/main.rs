@@ -5,8 +5,10 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
 use itertools::Itertools;
+use tracing::info_span;
 use z3::{
     ast::{Ast, BV},
     Config, Context, Optimize, SatResult,
@@ -16,12 +18,16 @@ use z3::{
 enum Part {
     P1,
     P2,
+    /// Check the input against the assumptions the solvers make
+    Lint,
 }
 
 #[derive(Parser, Debug)]
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type Num = u64;
@@ -151,18 +157,84 @@ fn simulate(
     Ok(true)
 }
 
+// P2 hard-codes one iteration of the program loop as
+// b = (a & 7) ^ 1; c = a >> b; out (b ^ c ^ 4) & 7; a >>= 3
+fn expected_output(a: Num) -> Num {
+    let b = (a & 7) ^ 1;
+    let c = a >> b;
+    (b ^ c ^ 4) % 8
+}
+
+fn assumption_violations(program: &Program) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if !program.ends_with(&[3, 0]) {
+        violations.push("program does not end with `jnz 0`".to_string());
+    }
+
+    let mut instructions = program
+        .iter()
+        .copied()
+        .tuples()
+        // bxc ignores its operand
+        .map(|(opcode, operand)| (opcode, if opcode == 4 { 0 } else { operand }))
+        .collect_vec();
+    instructions.sort_unstable();
+    let expected = [
+        (0, 3),
+        (1, 1),
+        (1, 4),
+        (2, 4),
+        (3, 0),
+        (4, 0),
+        (5, 5),
+        (7, 5),
+    ];
+    if program.len() % 2 != 0 || instructions != expected {
+        violations.push(format!(
+            "program instructions {instructions:?} differ from the reverse-engineered {expected:?}"
+        ));
+    }
+
+    if violations.is_empty() {
+        // the instructions match, so check they are also in an equivalent order
+        let samples = (0..64).chain([0o1234_5670, 0xdead_beef, Num::MAX >> 3]);
+        for a in samples {
+            let mut registers = Registers { a, b: 0, c: 0 };
+            let mut first_output = None;
+            let res = simulate(&mut registers, program, |n| {
+                first_output = Some(n);
+                true
+            });
+
+            if res.is_err() || first_output != Some(expected_output(a)) {
+                violations.push(format!(
+                    "program output for a = {a} differs from the reverse-engineered loop"
+                ));
+                break;
+            }
+        }
+    }
+
+    violations
+}
+
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
 
-    let mut inp = stdin().lines();
-    let end_of_input = || anyhow!("Unexpected end of input");
-    let a = parse_register(&mut inp)?;
-    let b = parse_register(&mut inp)?;
-    let c = parse_register(&mut inp)?;
-    let _ = inp.next().ok_or_else(end_of_input)?;
-    let program = parse_program(&mut inp)?;
+    let (mut registers, program) = info_span!("parse").in_scope(|| -> Result<_> {
+        let mut inp = stdin().lines();
+        let end_of_input = || anyhow!("Unexpected end of input");
+        let a = parse_register(&mut inp)?;
+        let b = parse_register(&mut inp)?;
+        let c = parse_register(&mut inp)?;
+        let _ = inp.next().ok_or_else(end_of_input)?;
+        let program = parse_program(&mut inp)?;
 
-    let mut registers = Registers { a, b, c };
+        Ok((Registers { a, b, c }, program))
+    })?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     match args.part {
         Part::P1 => {
@@ -173,7 +245,13 @@ fn main() -> Result<()> {
             })?;
             println!("{}", out.into_iter().map(|n| n.to_string()).join(","));
         }
-        Part::P2 => {
+        Part::P2 | Part::Lint => {
+            aoc_common::check_assumptions(assumption_violations(&program))?;
+            if let Part::Lint = args.part {
+                println!("All input assumptions hold");
+                return Ok(());
+            }
+
             // Solve system of equations based on reverse-engineered input
             let cfg = Config::new();
             let ctx = Context::new(&cfg);
@@ -4,11 +4,13 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
-    P1 { n: usize },
+    P1,
     P2,
 }
 
@@ -16,9 +18,18 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 
-    height: usize,
+    /// Largest x coordinate in the memory space
+    #[arg(long, global = true, default_value_t = 70)]
     width: usize,
+    /// Largest y coordinate in the memory space
+    #[arg(long, global = true, default_value_t = 70)]
+    height: usize,
+    /// Number of bytes which have fallen for part 1
+    #[arg(long, global = true, default_value_t = 1024)]
+    bytes: usize,
 }
 
 type Coords = (usize, usize);
@@ -29,7 +40,7 @@ fn parse_coords(inp: impl BufRead) -> impl Iterator<Item = Result<Coords>> {
         let (xs, ys) = line
             .split_once(',')
             .ok_or_else(|| anyhow!("Invalid input"))?;
-        Ok((xs.parse()?, ys.parse()?))
+        Ok((ys.parse()?, xs.parse()?))
     })
 }
 
@@ -82,12 +93,14 @@ fn find_path(corrupted: &HashSet<Coords>, height: usize, width: usize) -> Option
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let session = args.common.init()?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     match args.part {
-        Part::P1 { n } => {
+        Part::P1 => {
             let corrupted = parse_coords(stdin().lock())
-                .take(n)
+                .take(args.bytes)
                 .collect::<Result<HashSet<_>>>()?;
             let Some(path) = find_path(&corrupted, args.height, args.width) else {
                 bail!("Failed to find path");
@@ -98,12 +111,14 @@ fn main() -> Result<()> {
             let mut corrupted = HashSet::new();
             let mut path: HashSet<(usize, usize)> =
                 HashSet::from_iter(find_path(&corrupted, args.height, args.width).unwrap());
+            let progress = session.progress("bytes", None);
             for coord in parse_coords(stdin().lock()) {
                 let coord = coord?;
+                progress.inc(1)?;
                 corrupted.insert(coord);
                 if path.contains(&coord) {
                     let Some(new_path) = find_path(&corrupted, args.height, args.width) else {
-                        println!("{},{}", coord.0, coord.1);
+                        println!("{},{}", coord.1, coord.0);
                         break;
                     };
                     path = HashSet::from_iter(new_path);
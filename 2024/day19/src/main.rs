@@ -1,10 +1,9 @@
-use std::{
-    collections::HashMap,
-    io::{stdin, Error},
-};
+use std::io::{stdin, Error};
 
 use anyhow::{Context, Result};
+use aoc_common::{memo::Memo, CommonArgs};
 use clap::{Parser, Subcommand};
+use tracing::{info, info_span};
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -16,6 +15,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 fn parse_towels(inp: &mut impl Iterator<Item = Result<String, Error>>) -> Result<Vec<String>> {
@@ -29,10 +30,10 @@ fn patterns(
     inp.map(|line| Ok(line?))
 }
 
-fn count_ways_recursive<'a>(
-    memo: &mut HashMap<String, usize>,
+fn count_ways_recursive(
+    memo: &mut Memo<String, usize>,
     towels: &Vec<String>,
-    pattern: &'a str,
+    pattern: &str,
 ) -> usize {
     if pattern.is_empty() {
         return 1;
@@ -55,14 +56,16 @@ fn count_ways_recursive<'a>(
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
     let mut inp = stdin().lines();
-    let towels = parse_towels(&mut inp)?;
+    let towels = info_span!("parse").in_scope(|| parse_towels(&mut inp))?;
     inp.next().context("Unexpected end of input")??;
 
+    let _solve = info_span!("solve", part = ?args.part).entered();
     let mut count = 0;
     let mut total_ways = 0;
-    let mut memo = HashMap::new();
+    let mut memo = Memo::new();
     for pat in patterns(&mut inp) {
         let pat = pat?;
         let ways = count_ways_recursive(&mut memo, &towels, &pat);
@@ -71,6 +74,7 @@ fn main() -> Result<()> {
         }
         total_ways += ways;
     }
+    info!(memo = %memo.stats(), "search finished");
 
     match args.part {
         Part::P1 => println!("{count}"),
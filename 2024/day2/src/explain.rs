@@ -0,0 +1,89 @@
+use std::io::{self, Write};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    policy::{SafetyPolicy, Violation},
+    Report,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for reading
+    Table,
+    /// One JSON object per report
+    Json,
+}
+
+/// Why a report is or isn't safe
+#[derive(Serialize)]
+struct Diagnosis<'a> {
+    /// Line number of the report in the input
+    report: usize,
+    levels: &'a Report,
+    safe: bool,
+    /// The first step breaking the rules, before any removals
+    violation: Option<Violation>,
+    /// Indices of the levels to remove to make the report safe, if possible
+    removals: Option<Vec<usize>>,
+}
+
+fn diagnose<'a>(policy: &SafetyPolicy, report: usize, levels: &'a Report) -> Diagnosis<'a> {
+    let removals = policy.removals(levels);
+    Diagnosis {
+        report,
+        levels,
+        safe: removals.is_some(),
+        violation: policy.first_violation(levels),
+        removals,
+    }
+}
+
+fn describe_violation(violation: Option<&Violation>) -> String {
+    violation.map_or_else(
+        || "-".to_string(),
+        |v| format!("level {} ({} -> {}): {}", v.index, v.prev, v.level, v.rule),
+    )
+}
+
+fn describe_removals(levels: &Report, removals: Option<&Vec<usize>>) -> String {
+    match removals {
+        None => "-".to_string(),
+        Some(removals) if removals.is_empty() => "none needed".to_string(),
+        Some(removals) => removals
+            .iter()
+            .map(|&i| format!("level {i} ({})", levels[i]))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+pub fn explain(policy: &SafetyPolicy, reports: &[Report], format: Format) -> Result<()> {
+    let mut out = io::stdout().lock();
+    if let Format::Table = format {
+        writeln!(
+            out,
+            "{:>6}  {:<4}  {:<40}  remove",
+            "report", "safe", "first violation"
+        )?;
+    }
+
+    for (i, levels) in reports.iter().enumerate() {
+        let diagnosis = diagnose(policy, i + 1, levels);
+        match format {
+            Format::Table => writeln!(
+                out,
+                "{:>6}  {:<4}  {:<40}  {}",
+                diagnosis.report,
+                if diagnosis.safe { "yes" } else { "no" },
+                describe_violation(diagnosis.violation.as_ref()),
+                describe_removals(levels, diagnosis.removals.as_ref()),
+            )?,
+            Format::Json => writeln!(out, "{}", serde_json::to_string(&diagnosis)?)?,
+        }
+    }
+
+    Ok(())
+}
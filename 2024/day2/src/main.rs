@@ -0,0 +1,91 @@
+mod explain;
+mod policy;
+
+use anyhow::{ensure, Result};
+use aoc_common::CommonArgs;
+use clap::{Parser, Subcommand};
+use explain::Format;
+use policy::{Monotonicity, SafetyPolicy};
+use std::{
+    io::{stdin, BufRead},
+    num::ParseIntError,
+};
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Explain why each report is or isn't safe
+    Explain {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Smallest allowed difference between adjacent levels
+    #[arg(long, global = true, default_value_t = 1)]
+    min_step: u64,
+    /// Largest allowed difference between adjacent levels
+    #[arg(long, global = true, default_value_t = 3)]
+    max_step: u64,
+    #[arg(long, global = true, value_enum, default_value_t = Monotonicity::Strict)]
+    monotonicity: Monotonicity,
+    /// Most levels which may be removed to make a report safe (default: 0 for p1, 1 otherwise)
+    #[arg(long, global = true)]
+    max_removals: Option<usize>,
+}
+
+type Report = Vec<u64>;
+
+fn parse_report(line: &str) -> Result<Vec<u64>, ParseIntError> {
+    line.split_whitespace().map(str::parse).collect()
+}
+
+fn parse_reports(inp: impl BufRead) -> Result<Vec<Report>> {
+    let mut results = Vec::new();
+
+    for line in inp.lines() {
+        let line = line?;
+
+        results.push(parse_report(&line)?);
+    }
+
+    Ok(results)
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let reports = info_span!("parse").in_scope(|| parse_reports(stdin().lock()))?;
+
+    ensure!(
+        args.min_step <= args.max_step,
+        "--min-step must not exceed --max-step"
+    );
+    let policy = SafetyPolicy {
+        steps: args.min_step..=args.max_step,
+        monotonicity: args.monotonicity,
+        max_removals: args.max_removals.unwrap_or(match args.part {
+            Part::P1 => 0,
+            Part::P2 | Part::Explain { .. } => 1,
+        }),
+    };
+
+    let _solve = info_span!("solve", part = ?args.part).entered();
+    match args.part {
+        Part::P1 | Part::P2 => {
+            println!("{}", reports.iter().filter(|r| policy.is_safe(r)).count());
+        }
+        Part::Explain { format } => explain::explain(&policy, &reports, format)?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::Report;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Monotonicity {
+    /// Every level must be strictly higher, or strictly lower, than the last
+    Strict,
+    /// Levels may also repeat the last level
+    NonStrict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+/// The rules a report's levels must follow to be safe
+#[derive(Debug, Clone)]
+pub struct SafetyPolicy {
+    /// Allowed sizes of the difference between adjacent levels
+    pub steps: RangeInclusive<u64>,
+    pub monotonicity: Monotonicity,
+    /// Most levels which may be removed to make a report safe
+    pub max_removals: usize,
+}
+
+impl SafetyPolicy {
+    /// Whether `cur` may follow `prev` in a report going in `dir`
+    pub fn allows_step(&self, prev: u64, cur: u64, dir: Direction) -> bool {
+        if prev == cur {
+            return self.monotonicity == Monotonicity::NonStrict;
+        }
+        let rising = cur > prev;
+        rising == (dir == Direction::Increasing) && self.steps.contains(&cur.abs_diff(prev))
+    }
+
+    /// Fewest levels which must be removed from `report` to make every step go
+    /// in `dir`, or `None` if that takes more than `max_removals`.
+    ///
+    /// Since only `max_removals` levels may be skipped, each level can only
+    /// follow one of the `max_removals + 1` before it, so this takes
+    /// O(n * max_removals) time.
+    fn removals_going(&self, report: &Report, dir: Direction) -> Option<Vec<usize>> {
+        let n = report.len();
+        let k = self.max_removals;
+
+        // fewest removals among the first i + 1 levels if level i is kept last,
+        // with the level kept before it
+        let mut kept_last: Vec<Option<(usize, Option<usize>)>> = vec![None; n];
+        for i in 0..n {
+            // removing every level before this one
+            let mut best = (i, None);
+            for j in i.saturating_sub(k + 1)..i {
+                if let Some((removals, _)) = kept_last[j] {
+                    if self.allows_step(report[j], report[i], dir) {
+                        best = best.min((removals + (i - j - 1), Some(j)));
+                    }
+                }
+            }
+            if best.0 <= k {
+                kept_last[i] = Some(best);
+            }
+        }
+
+        let (removals, last) = (0..n)
+            .filter_map(|i| Some((kept_last[i]?.0 + (n - 1 - i), Some(i))))
+            .chain((n <= k).then_some((n, None)))
+            .min()?;
+        if removals > k {
+            return None;
+        }
+
+        let mut kept = vec![false; n];
+        let mut cur = last;
+        while let Some(i) = cur {
+            kept[i] = true;
+            cur = kept_last[i].and_then(|(_, prev)| prev);
+        }
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+
+    /// Fewest levels (by index) which must be removed from `report` to make it
+    /// safe, if at most `max_removals`
+    pub fn removals(&self, report: &Report) -> Option<Vec<usize>> {
+        [Direction::Increasing, Direction::Decreasing]
+            .into_iter()
+            .filter_map(|dir| self.removals_going(report, dir))
+            .min_by_key(Vec::len)
+    }
+
+    pub fn is_safe(&self, report: &Report) -> bool {
+        self.removals(report).is_some()
+    }
+
+    /// The first step in `report` which breaks the rules, ignoring removals
+    pub fn first_violation(&self, report: &Report) -> Option<Violation> {
+        let mut dir = None;
+        for (i, (&prev, &cur)) in report.iter().tuple_windows().enumerate() {
+            let step = cur.abs_diff(prev);
+            let rule = if step == 0 {
+                (self.monotonicity == Monotonicity::Strict).then_some(Rule::ZeroStep)
+            } else if cur > prev && dir == Some(Direction::Decreasing)
+                || cur < prev && dir == Some(Direction::Increasing)
+            {
+                Some(Rule::DirectionFlip)
+            } else if step > *self.steps.end() {
+                Some(Rule::StepTooBig)
+            } else if step < *self.steps.start() {
+                Some(Rule::StepTooSmall)
+            } else {
+                None
+            };
+
+            if let Some(rule) = rule {
+                return Some(Violation {
+                    index: i + 1,
+                    prev,
+                    level: cur,
+                    rule,
+                });
+            }
+            if step != 0 && dir.is_none() {
+                dir = Some(if cur > prev {
+                    Direction::Increasing
+                } else {
+                    Direction::Decreasing
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    ZeroStep,
+    StepTooSmall,
+    StepTooBig,
+    DirectionFlip,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ZeroStep => "zero step",
+            Self::StepTooSmall => "step too small",
+            Self::StepTooBig => "step too big",
+            Self::DirectionFlip => "direction flip",
+        })
+    }
+}
+
+/// A level which may not follow the one before it
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub index: usize,
+    pub prev: u64,
+    pub level: u64,
+    pub rule: Rule,
+}
@@ -4,7 +4,9 @@ use std::{
 };
 
 use anyhow::{bail, ensure, Context, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -16,6 +18,12 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Minimum number of picoseconds a cheat must save to be counted
+    #[arg(long, global = true, default_value_t = 100)]
+    threshold: usize,
 }
 
 type Coords = (usize, usize);
@@ -129,8 +137,10 @@ fn cheat_endpoints(map: &Map, start: &Coords, max_duration: usize) -> Vec<(usize
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let map = parse_maze(stdin().lock())?;
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let map = info_span!("parse").in_scope(|| parse_maze(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
     let dists = calc_dists(&map);
     let max_duration = match args.part {
         Part::P1 => 2,
@@ -150,7 +160,7 @@ fn main() -> Result<()> {
             for (elapsed, end) in cheat_endpoints(&map, &start, max_duration) {
                 let end_dist = dists[idx(&map, &end)].context("Missing distance")?;
 
-                if end_dist + elapsed + 100 <= start_dist {
+                if end_dist + elapsed + args.threshold <= start_dist {
                     count += 1;
                 }
             }
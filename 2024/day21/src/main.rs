@@ -1,19 +1,25 @@
-use std::{collections::HashMap, io::stdin, iter::repeat_n};
+use std::{io::stdin, iter::repeat_n};
 
 use anyhow::{bail, Result};
+use aoc_common::{memo::Memo, CommonArgs};
 use clap::{Parser, Subcommand};
 use itertools::Itertools;
+use tracing::{info, info_span};
 
 #[derive(Debug, Subcommand)]
 enum Part {
     P1,
     P2,
+    /// Check the input against the assumptions the solvers make
+    Lint,
 }
 
 #[derive(Parser, Debug)]
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 type Coords = (usize, usize);
 
@@ -119,7 +125,7 @@ const fn is_valid(robot_idx: usize, &(r, c): &Coords) -> bool {
 // cost to move robot robot_idx from 'from' to 'target' and then press it,
 // given that all robots up to robot_idx start on A
 fn search_rec(
-    memo: &mut HashMap<(usize, Coords, Coords), usize>,
+    memo: &mut Memo<(usize, Coords, Coords), usize>,
     num_robot_dirkey: usize,
     robot_idx: usize,
     from: &Coords,
@@ -202,18 +208,44 @@ fn search_rec(
     Ok(res)
 }
 
+fn assumption_violations(codes: &[String]) -> Vec<String> {
+    codes
+        .iter()
+        .enumerate()
+        .filter(|(_, code)| {
+            code.len() != 4
+                || !code.bytes().take(3).all(|b| b.is_ascii_digit())
+                || !code.ends_with('A')
+        })
+        .map(|(i, code)| {
+            format!(
+                "code {code:?} on line {} is not three digits then 'A'",
+                i + 1
+            )
+        })
+        .collect()
+}
+
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
 
+    let codes = info_span!("parse").in_scope(|| stdin().lines().collect::<Result<Vec<_>, _>>())?;
+    aoc_common::check_assumptions(assumption_violations(&codes))?;
+
+    let _solve = info_span!("solve", part = ?args.part).entered();
     let num_robot_dirkey = match args.part {
         Part::P1 => 2,
         Part::P2 => 25,
+        Part::Lint => {
+            println!("All input assumptions hold");
+            return Ok(());
+        }
     };
 
     let mut total = 0;
-    let mut memo = HashMap::new();
-    for s in stdin().lines() {
-        let s = s?;
+    let mut memo = Memo::new();
+    for s in codes {
         let numeric_part = s[..3].parse::<usize>()?;
         let mut path_len = 0;
         let mut coords = numpad_coords(Button::A)?;
@@ -234,6 +266,7 @@ fn main() -> Result<()> {
 
         total += path_len * numeric_part;
     }
+    info!(memo = %memo.stats(), "search finished");
 
     println!("{total}");
 
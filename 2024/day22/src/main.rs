@@ -5,8 +5,10 @@ use std::{
 };
 
 use anyhow::Result;
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
 use itertools::Itertools;
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -18,6 +20,12 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Number of new secret numbers each buyer generates
+    #[arg(long, global = true, default_value_t = 2000)]
+    iterations: usize,
 }
 
 struct SecretGenerator(usize);
@@ -53,24 +61,28 @@ impl Iterator for Buyer {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-
-    let buyers: Vec<Buyer> = stdin()
-        .lines()
-        .map(|line| -> Result<Buyer> {
-            let line = line?;
-            Ok(Buyer {
-                secret: SecretGenerator(line.as_str().parse::<usize>()?),
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+
+    let buyers: Vec<Buyer> = info_span!("parse").in_scope(|| {
+        stdin()
+            .lines()
+            .map(|line| -> Result<Buyer> {
+                let line = line?;
+                Ok(Buyer {
+                    secret: SecretGenerator(line.as_str().parse::<usize>()?),
+                })
             })
-        })
-        .try_collect()?;
+            .try_collect()
+    })?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     match args.part {
         Part::P1 => println!(
             "{}",
             buyers
                 .into_iter()
-                .map(|mut buyer| buyer.secret.nth(2000).unwrap())
+                .map(|mut buyer| buyer.secret.nth(args.iterations).unwrap())
                 .sum::<usize>()
         ),
         Part::P2 => {
@@ -78,7 +90,7 @@ fn main() -> Result<()> {
             for buyer in buyers {
                 let mut seen = HashSet::new();
                 for ((_, s1), (_, s2), (_, s3), (cur, s4)) in buyer
-                    .take(2000)
+                    .take(args.iterations)
                     .tuple_windows()
                     .map(|(prev, cur)| {
                         (
@@ -4,8 +4,10 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use aoc_common::{progress::Progress, CommonArgs};
 use clap::{Parser, Subcommand};
 use rand::{seq::IteratorRandom, thread_rng};
+use tracing::{info, info_span, instrument};
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -17,6 +19,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 fn connection(s: &str) -> Result<(String, String)> {
@@ -32,13 +36,16 @@ fn connections(s: impl BufRead) -> impl Iterator<Item = Result<(String, String)>
 
 type Graph = HashMap<String, HashSet<String>>;
 
+#[instrument(level = "debug", skip_all, fields(r = r.len(), p = p.len(), x = x.len()))]
 fn bron_kerbosch(
     graph: &Graph,
     r: &HashSet<String>,
     mut p: HashSet<String>,
     mut x: HashSet<String>,
     cb: &mut impl FnMut(&HashSet<String>),
-) {
+    progress: &Progress,
+) -> Result<()> {
+    progress.inc(1)?;
     if p.is_empty() && x.is_empty() {
         cb(r);
     } else {
@@ -53,15 +60,20 @@ fn bron_kerbosch(
                 p.intersection(vn).cloned().collect(),
                 x.intersection(vn).cloned().collect(),
                 cb,
-            );
+                progress,
+            )?;
             p.remove(&v);
             x.insert(v.clone());
         }
     }
+
+    Ok(())
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let session = args.common.init()?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let connections = connections(stdin().lock());
     let mut edges: Graph = HashMap::new();
@@ -95,17 +107,28 @@ fn main() -> Result<()> {
             let all_vertices = edges.keys().cloned().collect::<HashSet<_>>();
 
             let mut best = HashSet::new();
-            bron_kerbosch(
+            let mut maximal_cliques = 0;
+            let progress = session.progress("calls", None);
+            let res = bron_kerbosch(
                 &edges,
                 &HashSet::new(),
                 all_vertices,
                 HashSet::new(),
                 &mut |s| {
+                    maximal_cliques += 1;
                     if s.len() > best.len() {
                         best.clone_from(s);
                     }
                 },
+                &progress,
             );
+            info!(maximal_cliques, "search finished");
+            res.with_context(|| {
+                format!(
+                    "Found {maximal_cliques} maximal cliques, the largest with {} computers",
+                    best.len()
+                )
+            })?;
 
             let mut best = best.into_iter().collect::<Vec<_>>();
             best.sort();
@@ -0,0 +1,234 @@
+use std::{
+    collections::HashMap,
+    io::{stdin, BufRead},
+};
+
+use anyhow::{bail, Result};
+use aoc_common::{memo::Memo, CommonArgs};
+use clap::{Parser, Subcommand};
+use tracing::{info, info_span, warn};
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Check the input against the assumptions the solvers make
+    Lint,
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    And,
+    Or,
+    Xor,
+}
+
+impl Operator {
+    fn eval(&self, op1: bool, op2: bool) -> bool {
+        match self {
+            Operator::And => op1 && op2,
+            Operator::Or => op1 || op2,
+            Operator::Xor => op1 ^ op2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Gate {
+    op1: String,
+    op2: String,
+    op: Operator,
+}
+
+struct Input {
+    inits: HashMap<String, bool>,
+    gates: HashMap<String, Gate>,
+}
+
+fn input(inp: impl BufRead) -> Result<Input> {
+    let mut inits = HashMap::new();
+    let mut lines = inp.lines();
+    for line in &mut lines {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+
+        let Some((s, b)) = line.split_once(": ") else {
+            bail!("Failed to parse line");
+        };
+        inits.insert(
+            s.to_string(),
+            match b {
+                "1" => true,
+                "0" => false,
+                _ => bail!("Failed to parse init val"),
+            },
+        );
+    }
+
+    let mut gates = HashMap::new();
+    for line in lines {
+        let line = line?;
+        let mut toks = line.split_whitespace();
+        let (Some(op1), Some(op), Some(op2), Some(_), Some(out)) = (
+            toks.next().map(str::to_string),
+            toks.next(),
+            toks.next().map(str::to_string),
+            toks.next(),
+            toks.next().map(str::to_string),
+        ) else {
+            bail!("Failed to parse gate");
+        };
+        let op = match op {
+            "AND" => Operator::And,
+            "OR" => Operator::Or,
+            "XOR" => Operator::Xor,
+            _ => bail!("Failed to parse operator"),
+        };
+
+        gates.insert(out, Gate { op1, op2, op });
+    }
+
+    Ok(Input { inits, gates })
+}
+
+fn node(ch: char, i: usize) -> String {
+    format!("{ch}{i:0>2}")
+}
+
+const EVAL_MAX_DEPTH: usize = 10;
+fn try_eval(memo: &mut Memo<String, bool>, gates: &HashMap<String, Gate>, s: &str) -> Option<bool> {
+    if let Some(&val) = memo.get(s) {
+        return Some(val);
+    }
+
+    let gate = gates.get(s)?;
+    let Some(out) = memo.descend(|memo| {
+        let op1 = try_eval(memo, gates, &gate.op1)?;
+        let op2 = try_eval(memo, gates, &gate.op2)?;
+        Some(gate.op.eval(op1, op2))
+    }) else {
+        warn!(wire = %s, "recursion depth exceeded");
+        return None;
+    };
+
+    let out = out?;
+    memo.insert(s.to_string(), out);
+    Some(out)
+}
+
+// Generated by manually inspecting suspicious gates
+const SWAPPED_WIRES: [&str; 8] = ["z11", "wpd", "skh", "jqf", "z19", "mdd", "wts", "z37"];
+
+/// Evaluate the number on the `z` wires, stopping at the first which doesn't
+/// exist or can't be evaluated
+fn eval_z(memo: &mut Memo<String, bool>, gates: &HashMap<String, Gate>) -> usize {
+    let mut z = 0;
+    for i in 0.. {
+        let Some(val) = try_eval(memo, gates, &node('z', i)) else {
+            break;
+        };
+        z |= (val as usize) << i;
+    }
+
+    z
+}
+
+/// Pairs of `x` and `y` values which exercise every bit and carry of an adder
+/// with `bits` input bits
+fn adder_samples(bits: usize) -> Vec<(usize, usize)> {
+    let mask = (1 << bits) - 1;
+    let mut res = vec![(0, 0), (mask, 1), (1, mask), (mask, mask)];
+    for i in 0..bits {
+        res.extend([(1 << i, 0), (0, 1 << i), (1 << i, 1 << i)]);
+    }
+
+    res
+}
+
+fn assumption_violations(inp: &Input) -> Vec<String> {
+    let mut violations = SWAPPED_WIRES
+        .iter()
+        .filter(|wire| !inp.gates.contains_key(**wire))
+        .map(|wire| format!("hard-coded swapped wire {wire} is not a gate output"))
+        .collect::<Vec<_>>();
+    if !violations.is_empty() {
+        return violations;
+    }
+
+    // with the wires swapped back, the circuit should add x and y
+    let mut gates = inp.gates.clone();
+    for pair in SWAPPED_WIRES.chunks(2) {
+        let first = gates.remove(pair[0]).unwrap();
+        let second = gates.insert(pair[1].to_string(), first).unwrap();
+        gates.insert(pair[0].to_string(), second);
+    }
+
+    let bits = inp.inits.keys().filter(|k| k.starts_with('x')).count();
+    for (x, y) in adder_samples(bits) {
+        let mut inits = HashMap::new();
+        for i in 0..bits {
+            inits.insert(node('x', i), x >> i & 1 == 1);
+            inits.insert(node('y', i), y >> i & 1 == 1);
+        }
+        let mut memo = Memo::from(inits).with_depth_limit(EVAL_MAX_DEPTH);
+        let z = eval_z(&mut memo, &gates);
+        if z != x + y {
+            violations.push(format!(
+                "with the hard-coded wires swapped, {x} + {y} gives {z}, not {}",
+                x + y
+            ));
+            break;
+        }
+    }
+
+    violations
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let inp = info_span!("parse").in_scope(|| input(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
+
+    match args.part {
+        Part::P1 => {
+            let mut memo = Memo::from(inp.inits).with_depth_limit(EVAL_MAX_DEPTH);
+            let z = eval_z(&mut memo, &inp.gates);
+            info!(memo = %memo.stats(), "evaluation finished");
+            println!("{z}");
+        }
+        Part::P2 | Part::Lint => {
+            aoc_common::check_assumptions(assumption_violations(&inp))?;
+            if let Part::Lint = args.part {
+                println!("All input assumptions hold");
+                return Ok(());
+            }
+
+            println!("Suspicious gates:");
+            for (n, gate) in &inp.gates {
+                if !n.starts_with('z') {
+                    continue;
+                }
+                if !matches!(gate.op, Operator::Xor) {
+                    println!("{n} is not XOR")
+                }
+            }
+
+            let mut v = SWAPPED_WIRES;
+            v.sort_unstable();
+            println!("{}", v.join(","));
+        }
+    }
+
+    Ok(())
+}
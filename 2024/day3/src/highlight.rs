@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+
+use crate::{isa::Effect, machine::Step, scan::NearMiss};
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// Operations which changed a register
+    Executed,
+    /// Operations skipped while instructions were disabled
+    Disabled,
+    Control,
+    NearMiss,
+}
+
+impl Style {
+    const fn code(self) -> &'static str {
+        match self {
+            Self::Executed => "\x1b[1;32m",
+            Self::Disabled => "\x1b[2;32m",
+            Self::Control => "\x1b[1;36m",
+            Self::NearMiss => "\x1b[4;31m",
+        }
+    }
+}
+
+/// Write memory with its operations and near misses picked out in ANSI colors
+pub fn highlight(s: &str, steps: &[Step], near_misses: &[NearMiss]) -> io::Result<()> {
+    let mut styles = vec![None; s.len()];
+    for near_miss in near_misses {
+        styles[near_miss.span.clone()].fill(Some(Style::NearMiss));
+    }
+    // operations can start inside near misses, which they take precedence over
+    for step in steps {
+        let style = match step.op.instr.effect {
+            Effect::Control(_) => Style::Control,
+            Effect::Accumulate(_) if step.executed => Style::Executed,
+            Effect::Accumulate(_) => Style::Disabled,
+        };
+        styles[step.op.span.clone()].fill(Some(style));
+    }
+
+    let mut out = io::stdout().lock();
+    let mut cur = None;
+    for (i, c) in s.char_indices() {
+        if styles[i] != cur {
+            cur = styles[i];
+            write!(out, "{RESET}{}", cur.map_or("", Style::code))?;
+        }
+        write!(out, "{c}")?;
+    }
+    if cur.is_some() {
+        write!(out, "{RESET}")?;
+    }
+    if !s.ends_with('\n') {
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
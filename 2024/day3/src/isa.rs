@@ -0,0 +1,126 @@
+use std::{
+    fmt::{self, Debug, Display},
+    ops::Range,
+};
+
+use aoc_common::parsers::num;
+use nom::{character::complete::char, sequence::separated_pair, IResult};
+
+use crate::{machine::Machine, scan::Position};
+
+/// Parses an instruction's arguments, between its parentheses
+pub type ArgParser = fn(&str) -> IResult<&str, Vec<i64>>;
+
+pub enum Effect {
+    /// Add a value computed from the arguments to the current register, if
+    /// instructions are enabled. `None` if computing it overflows.
+    Accumulate(fn(&[i64]) -> Option<i64>),
+    /// Change the machine's state, even while instructions are disabled
+    Control(fn(&mut Machine, &[i64])),
+}
+
+/// An instruction which may appear in memory as `name(args)`
+pub struct Instruction {
+    pub name: &'static str,
+    pub args: ArgParser,
+    pub effect: Effect,
+}
+
+impl Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+fn no_args(input: &str) -> IResult<&str, Vec<i64>> {
+    Ok((input, Vec::new()))
+}
+
+fn one_arg(input: &str) -> IResult<&str, Vec<i64>> {
+    let (input, x) = num(input)?;
+
+    Ok((input, vec![x]))
+}
+
+fn two_args(input: &str) -> IResult<&str, Vec<i64>> {
+    let (input, (x, y)) = separated_pair(num, char(','), num)(input)?;
+
+    Ok((input, vec![x, y]))
+}
+
+pub const DO: Instruction = Instruction {
+    name: "do",
+    args: no_args,
+    effect: Effect::Control(|m, _| m.enabled = true),
+};
+
+pub const DONT: Instruction = Instruction {
+    name: "don't",
+    args: no_args,
+    effect: Effect::Control(|m, _| m.enabled = false),
+};
+
+pub const MUL: Instruction = Instruction {
+    name: "mul",
+    args: two_args,
+    effect: Effect::Accumulate(|args| args[0].checked_mul(args[1])),
+};
+
+/// Every known instruction
+pub const INSTRUCTIONS: &[Instruction] = &[
+    DO,
+    DONT,
+    MUL,
+    Instruction {
+        name: "add",
+        args: two_args,
+        effect: Effect::Accumulate(|args| args[0].checked_add(args[1])),
+    },
+    Instruction {
+        name: "sub",
+        args: two_args,
+        effect: Effect::Accumulate(|args| args[0].checked_sub(args[1])),
+    },
+    Instruction {
+        name: "max",
+        args: two_args,
+        effect: Effect::Accumulate(|args| Some(args[0].max(args[1]))),
+    },
+    // save whether instructions are enabled, until the matching `pop()`
+    Instruction {
+        name: "push",
+        args: no_args,
+        effect: Effect::Control(|m, _| m.push_scope()),
+    },
+    Instruction {
+        name: "pop",
+        args: no_args,
+        effect: Effect::Control(|m, _| m.pop_scope()),
+    },
+    // switch which register instructions accumulate into
+    Instruction {
+        name: "reg",
+        args: one_arg,
+        effect: Effect::Control(|m, args| m.register = args[0]),
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Instruction> {
+    INSTRUCTIONS.iter().find(|instr| instr.name == name)
+}
+
+/// An instruction found in memory, with its arguments
+pub struct Operation {
+    pub instr: &'static Instruction,
+    pub args: Vec<i64>,
+    /// Where the operation was found, in bytes
+    pub span: Range<usize>,
+    pub pos: Position,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = self.args.iter().map(i64::to_string).collect::<Vec<_>>();
+        write!(f, "{}({})", self.instr.name, args.join(","))
+    }
+}
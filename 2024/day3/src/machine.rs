@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use crate::isa::{Effect, Operation};
+
+pub struct Machine {
+    pub enabled: bool,
+    /// Whether instructions were enabled at each unmatched `push()`
+    scopes: Vec<bool>,
+    /// The register instructions accumulate into
+    pub register: i64,
+    pub registers: BTreeMap<i64, i64>,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            scopes: Vec::new(),
+            register: 0,
+            registers: BTreeMap::new(),
+        }
+    }
+}
+
+/// One operation's execution
+pub struct Step<'a> {
+    pub op: &'a Operation,
+    /// False if the operation was skipped because instructions were disabled
+    pub executed: bool,
+    pub register: i64,
+    /// The register's value afterwards
+    pub value: i64,
+}
+
+impl Machine {
+    /// Add to the current register, or `None` if it would overflow
+    pub fn accumulate(&mut self, value: i64) -> Option<()> {
+        let register = self.registers.entry(self.register).or_default();
+        *register = register.checked_add(value)?;
+        Some(())
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(self.enabled);
+    }
+
+    /// An unmatched `pop()` re-enables instructions, as at the start
+    pub fn pop_scope(&mut self) {
+        self.enabled = self.scopes.pop().unwrap_or(true);
+    }
+
+    pub fn value(&self, register: i64) -> i64 {
+        self.registers.get(&register).copied().unwrap_or(0)
+    }
+
+    pub fn step<'a>(&mut self, op: &'a Operation) -> Result<Step<'a>> {
+        let executed = match op.instr.effect {
+            Effect::Control(exec) => {
+                exec(self, &op.args);
+                true
+            }
+            Effect::Accumulate(f) if self.enabled => {
+                f(&op.args)
+                    .and_then(|value| self.accumulate(value))
+                    .with_context(|| format!("{} {op} overflows r{}", op.pos, self.register))?;
+                true
+            }
+            Effect::Accumulate(_) => false,
+        };
+
+        Ok(Step {
+            op,
+            executed,
+            register: self.register,
+            value: self.value(self.register),
+        })
+    }
+}
+
+/// Run `ops` on a fresh machine, returning it with the trace of every step
+pub fn run(ops: &[Operation]) -> Result<(Machine, Vec<Step<'_>>)> {
+    let mut machine = Machine::default();
+    let trace = ops
+        .iter()
+        .map(|op| machine.step(op))
+        .collect::<Result<_>>()?;
+
+    Ok((machine, trace))
+}
@@ -0,0 +1,134 @@
+mod highlight;
+mod isa;
+mod machine;
+mod scan;
+
+use anyhow::{Context, Result};
+use aoc_common::CommonArgs;
+use clap::{Parser, Subcommand};
+use isa::Instruction;
+use machine::Machine;
+use scan::{Found, Scanner};
+use std::io::{read_to_string, stdin};
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Interpret memory with a chosen instruction set
+    Run {
+        #[command(flatten)]
+        set: InstructionSet,
+        /// Print every operation with the register it left behind
+        #[arg(long)]
+        show_steps: bool,
+    },
+    /// List where each operation was found, and text which almost parsed as one
+    Scan {
+        #[command(flatten)]
+        set: InstructionSet,
+        /// Print the memory with operations and near misses in color instead
+        #[arg(long)]
+        highlight: bool,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+struct InstructionSet {
+    /// Instructions to recognize (default: all)
+    #[arg(long, value_delimiter = ',', value_parser = parse_instruction)]
+    instructions: Vec<&'static Instruction>,
+}
+
+fn parse_instruction(name: &str) -> Result<&'static Instruction> {
+    isa::find(name).with_context(|| format!("Unknown instruction {name:?}"))
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let set = match &args.part {
+        Part::P1 => vec![&isa::MUL],
+        Part::P2 => vec![&isa::DO, &isa::DONT, &isa::MUL],
+        Part::Run { set, .. } | Part::Scan { set, .. } if set.instructions.is_empty() => {
+            isa::INSTRUCTIONS.iter().collect()
+        }
+        Part::Run { set, .. } | Part::Scan { set, .. } => set.instructions.clone(),
+    };
+
+    // memory is scanned as it's read, so parsing and solving are interleaved
+    let _solve = info_span!("solve", part = ?args.part).entered();
+    match args.part {
+        Part::P1 | Part::P2 | Part::Run { .. } => {
+            let show_steps = matches!(
+                args.part,
+                Part::Run {
+                    show_steps: true,
+                    ..
+                }
+            );
+            let mut machine = Machine::default();
+            for found in Scanner::new(stdin().lock(), &set) {
+                let Found::Op(op) = found? else {
+                    continue;
+                };
+                let step = machine.step(&op)?;
+                if show_steps {
+                    let skipped = if step.executed { "" } else { " (disabled)" };
+                    println!("{}{skipped}: r{} = {}", step.op, step.register, step.value);
+                }
+            }
+
+            if let Part::Run { .. } = args.part {
+                let registers = machine
+                    .registers
+                    .iter()
+                    .map(|(r, v)| format!("r{r}={v}"))
+                    .collect::<Vec<_>>();
+                println!("{}", registers.join(" "));
+            } else {
+                println!("{}", machine.value(0));
+            }
+        }
+        Part::Scan {
+            highlight: true, ..
+        } => {
+            // the memory is written back out highlighted, so keep all of it
+            let s = read_to_string(stdin())?;
+            let scan = scan::scan(&s, &set);
+            let (_, steps) = machine::run(&scan.ops)?;
+            highlight::highlight(&s, &steps, &scan.near_misses)?;
+        }
+        Part::Scan { .. } => {
+            // near misses are listed after the operations, keeping only their text
+            let mut near_misses = Vec::new();
+            for found in Scanner::new(stdin().lock(), &set) {
+                match found? {
+                    Found::Op(op) => println!("{} {op}", op.pos),
+                    Found::NearMiss(near_miss) => near_misses.push(near_miss),
+                }
+            }
+
+            if !near_misses.is_empty() {
+                println!("\nNear misses:");
+            }
+            for near_miss in &near_misses {
+                println!(
+                    "{} {:?} is not a valid {}()",
+                    near_miss.pos, near_miss.text, near_miss.instr.name
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
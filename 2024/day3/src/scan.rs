@@ -0,0 +1,230 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead},
+    ops::Range,
+    str,
+};
+
+use nom::{bytes::complete::tag, character::complete::char, sequence::tuple, IResult};
+
+use crate::isa::{Instruction, Operation};
+
+/// Longest instruction recognized, which must fit in the scanner's lookahead.
+/// Instructions with two `i64` arguments take at most 46 bytes.
+const MAX_INSTRUCTION_LEN: usize = 64;
+
+/// Longest text reported for a near miss with no closing parenthesis
+const NEAR_MISS_MAX_LEN: usize = 16;
+
+/// Line and column, both counting from 1
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+/// Text which starts like an instruction, `name(`, but doesn't parse as one
+pub struct NearMiss {
+    pub instr: &'static Instruction,
+    /// The text itself, kept since the memory around it may not be
+    pub text: String,
+    pub span: Range<usize>,
+    pub pos: Position,
+}
+
+pub enum Found {
+    Op(Operation),
+    NearMiss(NearMiss),
+}
+
+/// Parse an instruction from `set` at the start of `input`, returning it with
+/// its arguments and length
+fn instruction(
+    set: &[&'static Instruction],
+    input: &str,
+) -> Option<(&'static Instruction, Vec<i64>, usize)> {
+    set.iter().find_map(|&instr| {
+        let res: IResult<_, _> = tuple((tag(instr.name), char('('), instr.args, char(')')))(input);
+        let (rest, (_, _, args, _)) = res.ok()?;
+        Some((instr, args, input.len() - rest.len()))
+    })
+}
+
+/// Length of a near miss at the start of `input`: up to its closing
+/// parenthesis, or the end of the line if that's sooner
+fn near_miss_len(input: &str) -> usize {
+    let mut len = 0;
+    for (i, c) in input.char_indices() {
+        if c == '\n' || i >= NEAR_MISS_MAX_LEN {
+            break;
+        }
+        len = i + c.len_utf8();
+        if c == ')' {
+            break;
+        }
+    }
+
+    len
+}
+
+/// Finds operations and near misses in memory read in chunks, holding only a
+/// chunk and an instruction's worth of lookahead at a time.
+///
+/// Most bytes can't start any instruction, so are skipped after one lookup.
+pub struct Scanner<'a, R> {
+    inp: R,
+    set: &'a [&'static Instruction],
+    /// Whether each byte starts the name of an instruction in the set
+    starts: [bool; 256],
+    buf: Vec<u8>,
+    /// Index in `buf` of the next byte to scan
+    i: usize,
+    /// Offset in the input of `buf[0]`
+    offset: usize,
+    pos: Position,
+    eof: bool,
+}
+
+impl<'a, R: BufRead> Scanner<'a, R> {
+    pub fn new(inp: R, set: &'a [&'static Instruction]) -> Self {
+        let mut starts = [false; 256];
+        for instr in set {
+            starts[usize::from(instr.name.as_bytes()[0])] = true;
+        }
+
+        Self {
+            inp,
+            set,
+            starts,
+            buf: Vec::new(),
+            i: 0,
+            offset: 0,
+            pos: Position { line: 1, col: 1 },
+            eof: false,
+        }
+    }
+
+    /// Drop the scanned bytes and read the next chunk
+    fn fill(&mut self) -> io::Result<()> {
+        self.buf.drain(..self.i);
+        self.offset += self.i;
+        self.i = 0;
+
+        let chunk = self.inp.fill_buf()?;
+        if chunk.is_empty() {
+            self.eof = true;
+        }
+        self.buf.extend_from_slice(chunk);
+        let len = chunk.len();
+        self.inp.consume(len);
+
+        Ok(())
+    }
+
+    fn advance(&mut self, n: usize) {
+        for &b in &self.buf[self.i..self.i + n] {
+            if b == b'\n' {
+                self.pos = Position {
+                    line: self.pos.line + 1,
+                    col: 1,
+                };
+            } else if b & 0xC0 != 0x80 {
+                // the first byte of a character
+                self.pos.col += 1;
+            }
+        }
+        self.i += n;
+    }
+
+    /// Look for an operation or near miss at the current byte, returning it
+    /// with the number of bytes to skip past
+    fn find_here(&self) -> Option<(Found, usize)> {
+        let window = &self.buf[self.i..self.buf.len().min(self.i + MAX_INSTRUCTION_LEN)];
+        // the window may end partway through a character
+        let window = match str::from_utf8(window) {
+            Ok(s) => s,
+            Err(e) => str::from_utf8(&window[..e.valid_up_to()]).unwrap(),
+        };
+        let start = self.offset + self.i;
+
+        if let Some((instr, args, len)) = instruction(self.set, window) {
+            let op = Operation {
+                instr,
+                args,
+                span: start..start + len,
+                pos: self.pos,
+            };
+            return Some((Found::Op(op), len));
+        }
+
+        let &instr = self.set.iter().find(|instr| {
+            window
+                .strip_prefix(instr.name)
+                .is_some_and(|after| after.starts_with('('))
+        })?;
+        let text = &window[..near_miss_len(window)];
+        let near_miss = NearMiss {
+            instr,
+            text: text.to_string(),
+            span: start..start + text.len(),
+            pos: self.pos,
+        };
+        // operations may start inside the near miss
+        Some((Found::NearMiss(near_miss), 1))
+    }
+}
+
+impl<R: BufRead> Iterator for Scanner<'_, R> {
+    type Item = io::Result<Found>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // keep a whole instruction of lookahead until the end of the input
+            if !self.eof && self.buf.len() - self.i < MAX_INSTRUCTION_LEN {
+                if let Err(e) = self.fill() {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+            if self.i == self.buf.len() {
+                return None;
+            }
+
+            if self.starts[usize::from(self.buf[self.i])] {
+                if let Some((found, len)) = self.find_here() {
+                    self.advance(len);
+                    return Some(Ok(found));
+                }
+            }
+            self.advance(1);
+        }
+    }
+}
+
+/// The operations found in memory, and the near misses between them
+pub struct Scan {
+    pub ops: Vec<Operation>,
+    pub near_misses: Vec<NearMiss>,
+}
+
+pub fn scan(s: &str, set: &[&'static Instruction]) -> Scan {
+    let mut res = Scan {
+        ops: Vec::new(),
+        near_misses: Vec::new(),
+    };
+    for found in Scanner::new(s.as_bytes(), set) {
+        // reading from memory can't fail
+        match found.unwrap() {
+            Found::Op(op) => res.ops.push(op),
+            Found::NearMiss(near_miss) => res.near_misses.push(near_miss),
+        }
+    }
+
+    res
+}
@@ -0,0 +1,97 @@
+mod render;
+mod search;
+mod template;
+
+use std::io::stdin;
+
+use anyhow::Result;
+use aoc_common::{grid::Grid, CommonArgs};
+use clap::{Parser, Subcommand};
+use render::Mode;
+use search::WordSearch;
+use template::{Symmetry, Template};
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// List every occurrence of the words, with where it starts and which way it goes
+    Find,
+    /// Print the grid with the words found in it picked out
+    Show {
+        #[arg(long, value_enum, default_value_t = Mode::Color)]
+        mode: Mode,
+    },
+    /// List every place the template matches, with the orientation it matched in
+    Match,
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Words to search for
+    #[arg(long, global = true, value_delimiter = ',', default_value = "XMAS")]
+    words: Vec<String>,
+    /// Let words run off one edge of the grid and continue from the opposite edge
+    #[arg(long, global = true)]
+    wrap: bool,
+    /// Shape to look for, with rows separated by '/' and '.' matching any letter
+    #[arg(long, global = true, default_value = template::X_MAS)]
+    template: Template,
+    /// Orientations of the template to look for
+    #[arg(long, global = true, value_enum, default_value_t = Symmetry::All)]
+    symmetry: Symmetry,
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+
+    let grid = info_span!("parse").in_scope(|| Grid::parse(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
+    match args.part {
+        Part::P1 => {
+            let search = WordSearch::new(&args.words)?;
+            println!("{}", search.find(&grid, args.wrap).len());
+        }
+        Part::P2 => {
+            let total = args
+                .template
+                .orientations(args.symmetry)
+                .iter()
+                .map(|t| t.find(&grid).count())
+                .sum::<usize>();
+            println!("{total}");
+        }
+        Part::Find => {
+            let search = WordSearch::new(&args.words)?;
+            for m in search.find(&grid, args.wrap) {
+                let (r, c) = m.start;
+                println!("{} {r},{c} {}", args.words[m.word], m.dir);
+            }
+        }
+        Part::Show { mode } => {
+            let search = WordSearch::new(&args.words)?;
+            let matches = search
+                .find(&grid, args.wrap)
+                .iter()
+                .map(|m| (m.word, search.cells(&grid, m, args.wrap)))
+                .collect::<Vec<_>>();
+            render::render(&grid, &matches, mode)?;
+        }
+        Part::Match => {
+            for t in args.template.orientations(args.symmetry) {
+                for (r, c) in t.find(&grid) {
+                    println!("{r},{c} {t}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use aoc_common::grid::{Grid, Position};
+
+const RESET: &str = "\x1b[0m";
+
+/// Foreground colors given to words in turn
+const WORD_COLORS: [&str; 6] = [
+    "\x1b[1;31m",
+    "\x1b[1;32m",
+    "\x1b[1;33m",
+    "\x1b[1;34m",
+    "\x1b[1;35m",
+    "\x1b[1;36m",
+];
+
+/// Backgrounds for cells in 1, 2, ... matches, from cool to hot
+const HEAT_COLORS: [&str; 5] = [
+    "\x1b[30;48;5;27m",
+    "\x1b[30;48;5;35m",
+    "\x1b[30;48;5;184m",
+    "\x1b[30;48;5;208m",
+    "\x1b[30;48;5;196m",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Color matched letters by the word they're part of
+    Color,
+    /// Replace letters which aren't part of any match with '.'
+    Dots,
+    /// Shade letters by how many matches pass through them
+    Heatmap,
+}
+
+/// Write the grid with the cells of each match picked out, where each match
+/// is the index of its word and the cells it covers
+pub fn render(grid: &Grid, matches: &[(usize, Vec<Position>)], mode: Mode) -> io::Result<()> {
+    // the last word through each cell, and how many matches pass through it
+    let mut words = vec![None; grid.rows * grid.cols];
+    let mut counts = vec![0; grid.rows * grid.cols];
+    for (word, cells) in matches {
+        for &(r, c) in cells {
+            words[r * grid.cols + c] = Some(*word);
+            counts[r * grid.cols + c] += 1;
+        }
+    }
+
+    let mut out = io::stdout().lock();
+    for r in 0..grid.rows {
+        let mut cur = None;
+        for c in 0..grid.cols {
+            let i = r * grid.cols + c;
+            let ch = grid.get((r, c));
+            if mode == Mode::Dots {
+                write!(out, "{}", if counts[i] > 0 { ch } else { '.' })?;
+                continue;
+            }
+
+            let style = match mode {
+                Mode::Color => words[i].map(|w| WORD_COLORS[w % WORD_COLORS.len()]),
+                Mode::Heatmap if counts[i] > 0 => {
+                    Some(HEAT_COLORS[(counts[i] - 1).min(HEAT_COLORS.len() - 1)])
+                }
+                Mode::Heatmap | Mode::Dots => None,
+            };
+            if style != cur {
+                cur = style;
+                write!(out, "{RESET}{}", cur.unwrap_or(""))?;
+            }
+            write!(out, "{ch}")?;
+        }
+        if cur.is_some() {
+            write!(out, "{RESET}")?;
+        }
+        writeln!(out)?;
+    }
+
+    if mode == Mode::Heatmap {
+        let max = counts.iter().max().copied().unwrap_or(0);
+        writeln!(out, "Most matches through one cell: {max}")?;
+    }
+
+    Ok(())
+}
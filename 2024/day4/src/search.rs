@@ -0,0 +1,242 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    iter,
+};
+
+use anyhow::{bail, Result};
+
+use aoc_common::grid::{Direction, Grid, Position};
+
+struct Node {
+    next: HashMap<char, usize>,
+    /// Node for the longest proper suffix of this node's prefix which is also
+    /// a prefix of some word
+    fail: usize,
+    /// Words ending here, including those ending at suffixes of this node's prefix
+    words: Vec<usize>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Self {
+            next: HashMap::new(),
+            fail: 0,
+            words: Vec::new(),
+        }
+    }
+}
+
+/// Aho-Corasick automaton finding every word in a list in one pass over the text
+struct Automaton {
+    nodes: Vec<Node>,
+}
+
+impl Automaton {
+    const ROOT: usize = 0;
+
+    fn new(words: &[Vec<char>]) -> Self {
+        let mut nodes = vec![Node::new()];
+        for (i, word) in words.iter().enumerate() {
+            let mut node = Self::ROOT;
+            for &ch in word {
+                node = match nodes[node].next.get(&ch) {
+                    Some(&next) => next,
+                    None => {
+                        let next = nodes.len();
+                        nodes.push(Node::new());
+                        nodes[node].next.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].words.push(i);
+        }
+
+        // nodes' suffixes are shorter, so are linked before them in breadth first order
+        let mut res = Self { nodes };
+        let mut queue = VecDeque::from([Self::ROOT]);
+        while let Some(node) = queue.pop_front() {
+            let next = res.nodes[node]
+                .next
+                .iter()
+                .map(|(&ch, &next)| (ch, next))
+                .collect::<Vec<_>>();
+            for (ch, next) in next {
+                let fail = if node == Self::ROOT {
+                    Self::ROOT
+                } else {
+                    res.step(res.nodes[node].fail, ch)
+                };
+                let inherited = res.nodes[fail].words.clone();
+                res.nodes[next].fail = fail;
+                res.nodes[next].words.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        res
+    }
+
+    fn step(&self, mut node: usize, ch: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&ch) {
+                return next;
+            } else if node == Self::ROOT {
+                return Self::ROOT;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Match {
+    /// Index of the word in the search's list
+    pub word: usize,
+    pub start: Position,
+    pub dir: Direction,
+}
+
+/// Finds words written in a grid in any of the eight directions
+pub struct WordSearch {
+    words: Vec<Vec<char>>,
+    automaton: Automaton,
+}
+
+impl WordSearch {
+    pub fn new(words: &[String]) -> Result<Self> {
+        if words.iter().any(String::is_empty) {
+            bail!("Can't search for an empty word");
+        }
+        let words = words
+            .iter()
+            .map(|w| w.chars().collect())
+            .collect::<Vec<_>>();
+        let automaton = Automaton::new(&words);
+
+        Ok(Self { words, automaton })
+    }
+
+    /// Cells covered by a match, from its first letter to its last
+    pub fn cells(&self, grid: &Grid, m: &Match, wrap: bool) -> Vec<Position> {
+        iter::successors(Some(m.start), |&pos| grid.step(pos, m.dir, wrap))
+            .take(self.words[m.word].len())
+            .collect()
+    }
+
+    /// Cells of each maximal line through the grid in `dir`, which are all
+    /// loops with `wrap`
+    fn lines(grid: &Grid, dir: Direction, wrap: bool) -> Vec<Vec<Position>> {
+        let mut res = Vec::new();
+        let mut seen = vec![false; grid.rows * grid.cols];
+        for start in grid.positions() {
+            // without wrapping, lines start where they can't be extended backwards
+            if seen[start.0 * grid.cols + start.1]
+                || (!wrap && grid.step(start, dir.reverse(), false).is_some())
+            {
+                continue;
+            }
+
+            let mut line = Vec::new();
+            let mut pos = Some(start);
+            while let Some(p) = pos.filter(|&p| !seen[p.0 * grid.cols + p.1]) {
+                seen[p.0 * grid.cols + p.1] = true;
+                line.push(p);
+                pos = grid.step(p, dir, wrap);
+            }
+            res.push(line);
+        }
+
+        res
+    }
+
+    /// Directions which don't retrace another's steps. With `wrap`, narrow
+    /// grids make some the same, e.g. every diagonal is horizontal on a grid
+    /// one row high.
+    fn distinct_dirs(grid: &Grid, wrap: bool) -> Vec<Direction> {
+        if !wrap || grid.rows == 0 || grid.cols == 0 {
+            return Direction::ALL.to_vec();
+        }
+        let wrapped = |dir: Direction| {
+            let (dr, dc) = dir.delta();
+            (
+                dr.rem_euclid(grid.rows as isize),
+                dc.rem_euclid(grid.cols as isize),
+            )
+        };
+
+        let mut res: Vec<Direction> = Vec::new();
+        for dir in Direction::ALL {
+            if res.iter().all(|&other| wrapped(other) != wrapped(dir)) {
+                res.push(dir);
+            }
+        }
+
+        res
+    }
+
+    /// Every occurrence of every word, in direction order.
+    ///
+    /// With `wrap`, words may continue from one edge of the grid to the
+    /// opposite edge, but never reuse a cell, so can't be longer than the
+    /// loop they're on. Single letter words match once per distinct direction.
+    pub fn find(&self, grid: &Grid, wrap: bool) -> Vec<Match> {
+        let max_len = self.words.iter().map(Vec::len).max().unwrap_or(0);
+        let mut res = Vec::new();
+
+        for dir in Self::distinct_dirs(grid, wrap) {
+            for line in Self::lines(grid, dir, wrap) {
+                // go round loops far enough to finish words starting anywhere on them
+                let steps = if wrap {
+                    line.len() + max_len - 1
+                } else {
+                    line.len()
+                };
+
+                let mut node = Automaton::ROOT;
+                for i in 0..steps {
+                    node = self.automaton.step(node, grid.get(line[i % line.len()]));
+                    for &word in &self.automaton.nodes[node].words {
+                        let len = self.words[word].len();
+                        let start = i + 1 - len;
+                        // later starts were found on the first time round
+                        if start < line.len() && len <= line.len() {
+                            res.push(Match {
+                                word,
+                                start: line[start],
+                                dir,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(grid: &str, words: &[&str]) -> usize {
+        let grid = Grid::parse(grid.as_bytes()).unwrap();
+        let words = words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        WordSearch::new(&words).unwrap().find(&grid, true).len()
+    }
+
+    #[test]
+    fn wrapped_words_dont_reuse_cells() {
+        assert_eq!(count("A", &["AAA"]), 0);
+        assert_eq!(count("XM", &["XMXM"]), 0);
+        assert_eq!(count("XM\nMX", &["XM"]), 4);
+    }
+
+    #[test]
+    fn wrapped_directions_dont_repeat() {
+        // every diagonal on one row is horizontal, and going up or down stays put
+        assert_eq!(count("XMAS", &["XMAS"]), 1);
+        assert_eq!(count("A", &["A"]), 1);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use anyhow::{bail, Error, Result};
+use clap::ValueEnum;
+
+use aoc_common::grid::{Grid, Position};
+
+/// Matches any letter in a template
+const WILDCARD: char = '.';
+
+/// Two MAS crossing at their A (part 2)
+pub const X_MAS: &str = "M.S/.A./M.S";
+
+/// Which orientations of a template to look for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Symmetry {
+    /// Only the template as written
+    None,
+    /// The template turned by any multiple of 90 degrees
+    Rotations,
+    /// Any rotation of the template or of its mirror image
+    All,
+}
+
+/// A small grid of letters and wildcards, written with its rows separated
+/// by `/`, like `M.S/.A./M.S`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    rows: usize,
+    cols: usize,
+    /// `None` for wildcards
+    cells: Vec<Option<char>>,
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lines = s.split('/').map(str::trim).collect::<Vec<_>>();
+        let cols = lines[0].chars().count();
+        if cols == 0 {
+            bail!("Template {s:?} has an empty row");
+        } else if let Some(line) = lines.iter().find(|l| l.chars().count() != cols) {
+            bail!("Template row {line:?} isn't {cols} letters long like the first");
+        }
+
+        let cells = lines
+            .iter()
+            .flat_map(|l| l.chars())
+            .map(|ch| (ch != WILDCARD).then_some(ch))
+            .collect();
+
+        Ok(Self {
+            rows: lines.len(),
+            cols,
+            cells,
+        })
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (r, row) in self.cells.chunks(self.cols).enumerate() {
+            if r > 0 {
+                f.write_str("/")?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.unwrap_or(WILDCARD))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Template {
+    fn get(&self, r: usize, c: usize) -> Option<char> {
+        self.cells[r * self.cols + c]
+    }
+
+    /// The template turned 90 degrees clockwise
+    fn rotate(&self) -> Self {
+        let cells = (0..self.cols)
+            .flat_map(|r| (0..self.rows).map(move |c| (r, c)))
+            .map(|(r, c)| self.get(self.rows - 1 - c, r))
+            .collect();
+
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            cells,
+        }
+    }
+
+    /// The template mirrored left to right
+    fn reflect(&self) -> Self {
+        let cells = self
+            .cells
+            .chunks(self.cols)
+            .flat_map(|row| row.iter().rev())
+            .copied()
+            .collect();
+
+        Self {
+            cells,
+            ..self.clone()
+        }
+    }
+
+    /// The distinct orientations of the template allowed by `symmetry`.
+    ///
+    /// Orientations which look the same are only included once, so that
+    /// symmetric templates aren't counted twice at the same place.
+    pub fn orientations(&self, symmetry: Symmetry) -> Vec<Self> {
+        let mut bases = vec![self.clone()];
+        if symmetry == Symmetry::All {
+            bases.push(self.reflect());
+        }
+
+        let mut res: Vec<Self> = Vec::new();
+        for base in bases {
+            let mut t = base;
+            for _ in 0..4 {
+                if !res.contains(&t) {
+                    res.push(t.clone());
+                }
+                if symmetry == Symmetry::None {
+                    break;
+                }
+                t = t.rotate();
+            }
+        }
+
+        res
+    }
+
+    /// Whether the template matches with its top left corner at `pos`
+    pub fn matches_at(&self, grid: &Grid, (r, c): Position) -> bool {
+        if r + self.rows > grid.rows || c + self.cols > grid.cols {
+            return false;
+        }
+
+        (0..self.rows)
+            .flat_map(|dr| (0..self.cols).map(move |dc| (dr, dc)))
+            .all(|(dr, dc)| {
+                self.get(dr, dc)
+                    .is_none_or(|ch| grid.get((r + dr, c + dc)) == ch)
+            })
+    }
+
+    /// Top left corners of every place the template matches
+    pub fn find<'a>(&'a self, grid: &'a Grid) -> impl Iterator<Item = Position> + 'a {
+        grid.positions().filter(|&pos| self.matches_at(grid, pos))
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::{self, Write},
+};
+
+use crate::{
+    order::{Ordering, Rules},
+    Rule, Update,
+};
+
+/// A rule broken by an update, with the positions of its pages, counting from 1
+struct Violation {
+    rule: Rule,
+    /// Position of the page which should have come second
+    after: usize,
+    /// Position of the page which should have come first
+    before: usize,
+}
+
+fn violations(rules: &Rules, update: &Update) -> Vec<Violation> {
+    let mut res = Vec::new();
+    for (i, &y) in update.iter().enumerate() {
+        for (j, &x) in update.iter().enumerate().skip(i + 1) {
+            if rules.requires(x, y) {
+                res.push(Violation {
+                    rule: (x, y),
+                    after: i + 1,
+                    before: j + 1,
+                });
+            }
+        }
+    }
+
+    res
+}
+
+/// Fewest swaps of two pages which put `update` in the order `target`, as
+/// pairs of positions counting from 1.
+///
+/// Each swap puts a page where it belongs, splitting it off from its cycle
+/// of misplaced pages, which is the most a single swap can do. Pages are
+/// tracked by value, which parsing makes sure is unique within an update.
+fn swaps(update: &Update, target: &[u64]) -> Vec<(usize, usize)> {
+    let mut cur = update.clone();
+    let mut positions = cur
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| (p, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut res = Vec::new();
+    for (i, &page) in target.iter().enumerate() {
+        if cur[i] == page {
+            continue;
+        }
+        let j = positions[&page];
+        positions.insert(cur[i], j);
+        positions.insert(page, i);
+        cur.swap(i, j);
+        res.push((i + 1, j + 1));
+    }
+
+    res
+}
+
+/// Rules between pages which never appear in the same update
+fn unexercised(rule_list: &[Rule], updates: &[Update]) -> Vec<Rule> {
+    let pairs = updates
+        .iter()
+        .flat_map(|u| u.iter().flat_map(|&x| u.iter().map(move |&y| (x, y))))
+        .collect::<HashSet<_>>();
+
+    rule_list
+        .iter()
+        .copied()
+        .filter(|rule| !pairs.contains(rule))
+        .collect()
+}
+
+/// Whether `rule` follows from the other rules between the pages of `update`,
+/// through a chain of pages between its two
+fn implied(rules: &Rules, update: &Update, (x, y): Rule) -> bool {
+    let mut seen = HashSet::from([x]);
+    let mut queue = VecDeque::from([x]);
+    while let Some(page) = queue.pop_front() {
+        for &next in update {
+            if (page, next) == (x, y) || !rules.requires(page, next) {
+                continue;
+            } else if next == y {
+                return true;
+            } else if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    false
+}
+
+/// Exercised rules which follow from other rules in every update they apply to,
+/// so removing them wouldn't change how any update is checked or ordered
+fn redundant(rules: &Rules, rule_list: &[Rule], updates: &[Update]) -> Vec<Rule> {
+    // the rule graph as a whole usually has cycles, so only chains of rules
+    // within an update count
+    let mut needed = HashSet::new();
+    let mut exercised = HashSet::new();
+    for update in updates {
+        for &x in update {
+            for &y in update {
+                if x != y && rules.requires(x, y) {
+                    exercised.insert((x, y));
+                    if !needed.contains(&(x, y)) && !implied(rules, update, (x, y)) {
+                        needed.insert((x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    rule_list
+        .iter()
+        .copied()
+        .filter(|rule| exercised.contains(rule) && !needed.contains(rule))
+        .collect()
+}
+
+fn list(rules: &[Rule]) -> String {
+    if rules.is_empty() {
+        return "none".to_string();
+    }
+    rules
+        .iter()
+        .map(|(x, y)| format!("{x}|{y}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Write why each incorrectly ordered update is wrong and how to fix it,
+/// then the rules which never or needn't apply
+pub fn explain(rules: &Rules, rule_list: &[Rule], updates: &[Update]) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for (i, update) in updates.iter().enumerate() {
+        let violations = violations(rules, update);
+        if violations.is_empty() {
+            continue;
+        }
+
+        let pages = update.iter().map(u64::to_string).collect::<Vec<_>>();
+        writeln!(out, "Update {}: {}", i + 1, pages.join(","))?;
+        for v in &violations {
+            let (x, y) = v.rule;
+            writeln!(
+                out,
+                "  breaks {x}|{y}: {y} at position {} is before {x} at position {}",
+                v.after, v.before
+            )?;
+        }
+        match rules.order(update) {
+            Ordering::Sorted { pages, ambiguous } => {
+                let swaps = swaps(update, &pages)
+                    .iter()
+                    .map(|(a, b)| format!("{a}<->{b}"))
+                    .collect::<Vec<_>>();
+                // the fewest swaps to some other valid order may be fewer still
+                let other = if ambiguous {
+                    " (one of several orders)"
+                } else {
+                    ""
+                };
+                writeln!(out, "  fix{other}: swap {}", swaps.join(" "))?;
+            }
+            Ordering::Cycle(cycle) => {
+                let cycle = cycle.iter().map(u64::to_string).collect::<Vec<_>>();
+                writeln!(out, "  no fix: pages {} form a cycle", cycle.join(", "))?;
+            }
+        }
+    }
+
+    writeln!(
+        out,
+        "Unexercised rules: {}",
+        list(&unexercised(rule_list, updates))
+    )?;
+    writeln!(
+        out,
+        "Redundant rules: {}",
+        list(&redundant(rules, rule_list, updates))
+    )?;
+
+    Ok(())
+}
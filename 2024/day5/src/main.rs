@@ -0,0 +1,182 @@
+mod explain;
+mod order;
+mod stream;
+
+use std::{
+    collections::HashSet,
+    io::{read_to_string, stdin, BufRead},
+};
+
+use anyhow::{bail, Result};
+use aoc_common::{parsers::num, CommonArgs};
+use clap::{Parser, Subcommand};
+use nom::{
+    character::complete::{char, line_ending, multispace1},
+    error::Error,
+    multi::separated_list1,
+    sequence::separated_pair,
+    Err, IResult,
+};
+use order::{Ordering, Rules};
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    #[command(flatten)]
+    Batch(Batch),
+    /// Answer a stream of RULE x|y, REMOVE x|y and UPDATE a,b,c lines as they arrive
+    Stream,
+}
+
+/// Subcommands which read all of the rules and updates before answering
+#[derive(Debug, Subcommand)]
+enum Batch {
+    P1,
+    P2,
+    /// Explain how each incorrectly ordered update breaks the rules, and which
+    /// rules are never or needn't be applied
+    Explain,
+    /// Check the input against the assumptions the solvers make
+    Lint,
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type Rule = (u64, u64);
+type Update = Vec<u64>;
+
+struct Input {
+    rules: Vec<Rule>,
+    updates: Vec<Update>,
+}
+
+fn rule(input: &str) -> IResult<&str, Rule> {
+    separated_pair(num, char('|'), num)(input)
+}
+
+fn update(input: &str) -> IResult<&str, Update> {
+    separated_list1(char(','), num)(input)
+}
+
+fn input(input: &str) -> IResult<&str, Input> {
+    let (input, rules) = separated_list1(line_ending, rule)(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, updates) = separated_list1(line_ending, update)(input)?;
+
+    Ok((input, Input { rules, updates }))
+}
+
+/// A page which appears more than once in an update, which no order can
+/// place consistently
+fn repeated_page(update: &[u64]) -> Option<u64> {
+    let mut seen = HashSet::new();
+    update.iter().copied().find(|&page| !seen.insert(page))
+}
+
+fn parse_input(r: impl BufRead) -> Result<Input> {
+    let s = read_to_string(r)?;
+    let (rest, input) = input(&s).map_err(Err::<Error<&str>>::to_owned)?;
+
+    // updates are the last lines parsed, one per line
+    let parsed_lines = s[..s.len() - rest.len()].trim_end().lines().count();
+    let first_update_line = parsed_lines - input.updates.len() + 1;
+    for (i, update) in input.updates.iter().enumerate() {
+        if let Some(page) = repeated_page(update) {
+            bail!(
+                "Line {}: page {page} appears more than once in update {}",
+                first_update_line + i,
+                i + 1
+            );
+        }
+    }
+
+    Ok(input)
+}
+
+/// Why the `i`th update (counting from 0) can't be put in order, or could be
+/// put in more than one, if so
+fn order_violation(i: usize, ordering: &Ordering) -> Option<String> {
+    match ordering {
+        Ordering::Cycle(cycle) => {
+            let cycle = cycle.iter().map(u64::to_string).collect::<Vec<_>>();
+            Some(format!(
+                "update {} can't be ordered, since pages {} must each come before the next, and the last before the first",
+                i + 1,
+                cycle.join(", ")
+            ))
+        }
+        Ordering::Sorted {
+            ambiguous: true, ..
+        } => Some(format!("update {} has more than one valid order", i + 1)),
+        Ordering::Sorted { .. } => None,
+    }
+}
+
+/// Updates which can't be put in order, or could be put in more than one
+fn assumption_violations(rules: &Rules, updates: &[Update]) -> Vec<String> {
+    updates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, update)| order_violation(i, &rules.order(update)))
+        .collect()
+}
+
+fn solve(part: &Batch, input: &Input) -> Result<()> {
+    let rules = Rules::new(&input.rules);
+    if let Batch::Lint = part {
+        aoc_common::check_assumptions(assumption_violations(&rules, &input.updates))?;
+    }
+    let _solve = info_span!("solve", part = ?part).entered();
+
+    let mut correct_sum = 0;
+    let mut incorrect_sum = 0;
+    // cycles and ambiguous orders only matter when correcting updates, so
+    // correctly ordered updates may have them
+    let mut violations = Vec::new();
+    for (i, update) in input.updates.iter().enumerate() {
+        match rules.order(update) {
+            Ordering::Sorted { pages, .. } if pages == *update => {
+                correct_sum += update[update.len() / 2];
+            }
+            Ordering::Sorted {
+                pages,
+                ambiguous: false,
+            } => incorrect_sum += pages[pages.len() / 2],
+            ordering => violations.extend(order_violation(i, &ordering)),
+        }
+    }
+    if let Batch::P2 = part {
+        aoc_common::check_assumptions(violations)?;
+    }
+
+    match part {
+        Batch::P1 => {
+            println!("{correct_sum}");
+        }
+        Batch::P2 => {
+            println!("{incorrect_sum}");
+        }
+        Batch::Explain => explain::explain(&rules, &input.rules, &input.updates)?,
+        Batch::Lint => println!("All input assumptions hold"),
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    match args.part {
+        Part::Batch(part) => {
+            let input = info_span!("parse").in_scope(|| parse_input(stdin().lock()))?;
+            solve(&part, &input)
+        }
+        Part::Stream => stream::stream(stdin().lock()),
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::Rule;
+
+pub enum Ordering {
+    /// The pages of an update in an order which follows every rule, keeping
+    /// them in their original order where the rules allow. `ambiguous` if
+    /// other orders follow every rule too.
+    Sorted { pages: Vec<u64>, ambiguous: bool },
+    /// Pages which must each come before the next, and the last before the first
+    Cycle(Vec<u64>),
+}
+
+/// The ordering rules, indexed for checking pairs of pages
+pub struct Rules {
+    /// Pages which must come before each page
+    deps: HashMap<u64, HashSet<u64>>,
+}
+
+impl Rules {
+    pub fn new(rules: &[Rule]) -> Self {
+        let mut res = Self {
+            deps: HashMap::new(),
+        };
+        for &rule in rules {
+            res.add(rule);
+        }
+
+        res
+    }
+
+    /// Add a rule, returning whether it's new
+    pub fn add(&mut self, (x, y): Rule) -> bool {
+        self.deps.entry(y).or_default().insert(x)
+    }
+
+    /// Remove a rule, returning whether it was there
+    pub fn remove(&mut self, (x, y): Rule) -> bool {
+        let Some(deps) = self.deps.get_mut(&y) else {
+            return false;
+        };
+        let removed = deps.remove(&x);
+        if deps.is_empty() {
+            self.deps.remove(&y);
+        }
+
+        removed
+    }
+
+    /// Whether a rule says `before` must come before `after`
+    pub fn requires(&self, before: u64, after: u64) -> bool {
+        self.deps.get(&after).is_some_and(|d| d.contains(&before))
+    }
+
+    /// Sort an update's pages with Kahn's algorithm over the rules between
+    /// them, always taking the earliest page whose dependencies are placed
+    pub fn order(&self, update: &[u64]) -> Ordering {
+        let n = update.len();
+        // pages which must come before each page, by index in the update
+        let mut preds = vec![Vec::new(); n];
+        let mut succs = vec![Vec::new(); n];
+        for (i, &x) in update.iter().enumerate() {
+            for (j, &y) in update.iter().enumerate() {
+                if i != j && self.requires(x, y) {
+                    preds[j].push(i);
+                    succs[i].push(j);
+                }
+            }
+        }
+
+        let mut unplaced = preds.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut ready = (0..n)
+            .filter(|&i| unplaced[i] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut pages = Vec::with_capacity(n);
+        let mut ambiguous = false;
+        while let Some(Reverse(i)) = ready.pop() {
+            // any of the other ready pages could have gone here instead
+            ambiguous |= !ready.is_empty();
+            pages.push(update[i]);
+            for &j in &succs[i] {
+                unplaced[j] -= 1;
+                if unplaced[j] == 0 {
+                    ready.push(Reverse(j));
+                }
+            }
+        }
+
+        if pages.len() < n {
+            return Ordering::Cycle(Self::cycle(update, &preds, &unplaced));
+        }
+
+        Ordering::Sorted { pages, ambiguous }
+    }
+
+    /// A cycle among the pages Kahn's algorithm couldn't place, each of which
+    /// still has an unplaced page which must come before it
+    fn cycle(update: &[u64], preds: &[Vec<usize>], unplaced: &[usize]) -> Vec<u64> {
+        let mut node = (0..update.len()).find(|&i| unplaced[i] > 0).unwrap();
+        let mut path = Vec::new();
+        let mut seen = HashMap::new();
+        // walk backwards through unplaced dependencies until one repeats
+        while !seen.contains_key(&node) {
+            seen.insert(node, path.len());
+            path.push(node);
+            node = *preds[node].iter().find(|&&p| unplaced[p] > 0).unwrap();
+        }
+
+        path[seen[&node]..]
+            .iter()
+            .rev()
+            .map(|&i| update[i])
+            .collect()
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, BufRead, Write};
+
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{all_consuming, map},
+    sequence::preceded,
+    IResult,
+};
+
+use crate::{
+    order::{Ordering, Rules},
+    repeated_page, rule, update, Rule, Update,
+};
+
+enum Command {
+    Add(Rule),
+    Remove(Rule),
+    Check(Update),
+}
+
+fn command(input: &str) -> IResult<&str, Command> {
+    all_consuming(alt((
+        map(preceded(tag("RULE "), rule), Command::Add),
+        map(preceded(tag("REMOVE "), rule), Command::Remove),
+        map(preceded(tag("UPDATE "), update), Command::Check),
+    )))(input)
+}
+
+fn pages(pages: &[u64]) -> String {
+    pages
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The answer to an update: whether it's in order, and its middle page once it is
+fn check(rules: &Rules, update: &Update) -> String {
+    if let Some(page) = repeated_page(update) {
+        return format!("error: page {page} appears more than once");
+    }
+
+    match rules.order(update) {
+        Ordering::Sorted { pages: sorted, .. } if sorted == *update => {
+            format!("ok {}", update[update.len() / 2])
+        }
+        Ordering::Sorted {
+            pages: sorted,
+            ambiguous,
+        } => {
+            let other = if ambiguous {
+                " (one of several orders)"
+            } else {
+                ""
+            };
+            format!(
+                "fixed{other} {} {}",
+                pages(&sorted),
+                sorted[sorted.len() / 2]
+            )
+        }
+        Ordering::Cycle(cycle) => format!("cycle {}", pages(&cycle)),
+    }
+}
+
+/// The answer to one command, after applying it to `rules`
+fn answer(rules: &mut Rules, command: Command) -> String {
+    match command {
+        Command::Add((x, y)) if rules.add((x, y)) => format!("added {x}|{y}"),
+        Command::Add((x, y)) => format!("already have {x}|{y}"),
+        Command::Remove((x, y)) if rules.remove((x, y)) => format!("removed {x}|{y}"),
+        Command::Remove((x, y)) => format!("no rule {x}|{y}"),
+        Command::Check(update) => check(rules, &update),
+    }
+}
+
+/// Answer a stream of `RULE x|y`, `REMOVE x|y` and `UPDATE a,b,c` lines one
+/// at a time, against the rules given so far.
+///
+/// Updates are answered with `ok` and their middle page if they're already in
+/// order, `fixed` with their corrected order and its middle page if not, or
+/// `cycle` with the pages which can't be ordered, or `error` if a page repeats.
+pub fn stream(inp: impl BufRead) -> Result<()> {
+    let mut rules = Rules::new(&[]);
+    let mut out = io::stdout().lock();
+    for line in inp.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // a bad line shouldn't end the stream
+        let answer = match command(line) {
+            Ok((_, command)) => answer(&mut rules, command),
+            Err(_) => format!("error: can't parse {line:?}"),
+        };
+        writeln!(out, "{answer}")?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+mod sim;
+
+use std::io::{stdin, BufRead};
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::{animation, CommonArgs};
+use clap::{Parser, Subcommand};
+use sim::Lab;
+use tracing::info_span;
+
+#[derive(Debug, Subcommand)]
+enum Part {
+    P1,
+    P2,
+    /// Print the lab each time the guard turns
+    Animate,
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+type Position = (usize, usize);
+
+struct Input {
+    rows: usize,
+    cols: usize,
+    obstructions: Vec<Position>,
+    init_pos: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    const fn rotate(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::South => Self::West,
+            Self::East => Self::South,
+            Self::West => Self::North,
+        }
+    }
+
+    /// Index of the direction in per-direction tables
+    const fn idx(self) -> usize {
+        match self {
+            Self::North => 0,
+            Self::South => 1,
+            Self::East => 2,
+            Self::West => 3,
+        }
+    }
+}
+
+fn parse_input(r: impl BufRead) -> Result<Input> {
+    let mut obstructions = Vec::new();
+    let mut init_pos = None;
+    let mut rows = 0;
+    let mut cols = 0;
+
+    for (r, line) in r.lines().enumerate() {
+        let line = line?;
+        rows = rows.max(r + 1);
+        for (c, chr) in line.char_indices() {
+            cols = cols.max(c + 1);
+            match chr {
+                '.' => continue,
+                '^' => init_pos = Some((r, c)),
+                '#' => obstructions.push((r, c)),
+                _ => bail!("Unexpected character '{}' in input", chr),
+            }
+        }
+    }
+
+    let init_pos = init_pos.ok_or_else(|| anyhow!("Could not find initial position"))?;
+
+    Ok(Input {
+        rows,
+        cols,
+        obstructions,
+        init_pos,
+    })
+}
+
+fn main() -> Result<()> {
+    let args = aoc_common::parse::<Args>()?;
+    let session = args.common.init()?;
+    let inp = info_span!("parse").in_scope(|| parse_input(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
+    let lab = Lab::new(&inp);
+
+    match args.part {
+        Part::P1 => println!("{}", lab.covered()),
+        Part::P2 => {
+            let progress = session.progress("candidates", None);
+            println!("{}", lab.loop_obstructions(&progress)?);
+        }
+        Part::Animate => {
+            for frame in lab.frames() {
+                animation::print_frame(&frame);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,266 @@
+use anyhow::Result;
+use aoc_common::progress::Progress;
+use tracing::{debug_span, info};
+
+use crate::{Direction, Input};
+
+/// A fixed size set of small integers
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Add `i`, returning whether it's new
+    fn insert(&mut self, i: usize) -> bool {
+        let new = !self.contains(i);
+        self.words[i / 64] |= 1 << (i % 64);
+        new
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.words[i / 64] &= !(1 << (i % 64));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Jump {
+    /// Last cell the guard reaches walking in a straight line
+    to: usize,
+    /// Whether the guard walks off the map from there, rather than turning
+    exits: bool,
+}
+
+/// The lab as a dense grid of cells numbered in row major order, with where
+/// the guard stops walking from each cell in each direction
+pub struct Lab {
+    rows: usize,
+    cols: usize,
+    blocked: BitSet,
+    start: usize,
+    jumps: Vec<[Jump; 4]>,
+}
+
+impl Lab {
+    pub fn new(inp: &Input) -> Self {
+        let (rows, cols) = (inp.rows, inp.cols);
+        let mut blocked = BitSet::new(rows * cols);
+        for &(r, c) in &inp.obstructions {
+            blocked.insert(r * cols + c);
+        }
+
+        let mut lab = Self {
+            rows,
+            cols,
+            blocked,
+            start: inp.init_pos.0 * cols + inp.init_pos.1,
+            jumps: Vec::new(),
+        };
+        let unset = Jump { to: 0, exits: true };
+        lab.jumps = vec![[unset; 4]; rows * cols];
+        // each cell's jump extends its neighbour's, so fill the neighbour first
+        lab.fill_jumps(0..rows * cols, [Direction::North, Direction::West]);
+        lab.fill_jumps((0..rows * cols).rev(), [Direction::South, Direction::East]);
+
+        lab
+    }
+
+    fn fill_jumps(&mut self, cells: impl Iterator<Item = usize>, dirs: [Direction; 2]) {
+        for cell in cells {
+            for dir in dirs {
+                self.jumps[cell][dir.idx()] = match self.step(cell, dir) {
+                    None => Jump {
+                        to: cell,
+                        exits: true,
+                    },
+                    Some(next) if self.blocked.contains(next) => Jump {
+                        to: cell,
+                        exits: false,
+                    },
+                    Some(next) => self.jumps[next][dir.idx()],
+                };
+            }
+        }
+    }
+
+    /// The neighbouring cell in `dir`, or `None` at the edge
+    const fn step(&self, cell: usize, dir: Direction) -> Option<usize> {
+        let (r, c) = (cell / self.cols, cell % self.cols);
+        match dir {
+            Direction::North if r > 0 => Some(cell - self.cols),
+            Direction::South if r < self.rows - 1 => Some(cell + self.cols),
+            Direction::West if c > 0 => Some(cell - 1),
+            Direction::East if c < self.cols - 1 => Some(cell + 1),
+            _ => None,
+        }
+    }
+
+    /// Where the guard stops walking from `cell` in `dir` with an extra
+    /// obstruction in the lab, or `None` if they walk off the map
+    fn jump(&self, cell: usize, dir: Direction, obstruction: usize) -> Option<usize> {
+        let jump = self.jumps[cell][dir.idx()];
+        let (r, c) = (cell / self.cols, cell % self.cols);
+        let (or, oc) = (obstruction / self.cols, obstruction % self.cols);
+
+        // steps to the extra obstruction, if it's straight ahead
+        let ahead = match dir {
+            Direction::North if oc == c && or < r => Some(r - or),
+            Direction::South if oc == c && or > r => Some(or - r),
+            Direction::West if or == r && oc < c => Some(c - oc),
+            Direction::East if or == r && oc > c => Some(oc - c),
+            _ => None,
+        };
+        let reach = r.abs_diff(jump.to / self.cols) + c.abs_diff(jump.to % self.cols);
+        match ahead {
+            // it's no further than where the guard would have stopped anyway
+            Some(steps) if steps <= reach => Some(match dir {
+                Direction::North => cell - (steps - 1) * self.cols,
+                Direction::South => cell + (steps - 1) * self.cols,
+                Direction::West => cell - (steps - 1),
+                Direction::East => cell + (steps - 1),
+            }),
+            _ => (!jump.exits).then_some(jump.to),
+        }
+    }
+
+    /// Each step of the guard's route, as the cell they step from and the
+    /// direction they step in
+    pub fn route(&self) -> Vec<(usize, Direction)> {
+        let mut res = Vec::new();
+        let (mut cell, mut dir) = (self.start, Direction::North);
+        while let Some(next) = self.step(cell, dir) {
+            if self.blocked.contains(next) {
+                dir = dir.rotate();
+            } else {
+                res.push((cell, dir));
+                cell = next;
+            }
+        }
+
+        res
+    }
+
+    /// Number of distinct cells the guard visits
+    pub fn covered(&self) -> usize {
+        let mut covered = BitSet::new(self.rows * self.cols);
+        covered.insert(self.start);
+        self.route()
+            .into_iter()
+            .filter(|&(cell, dir)| covered.insert(self.step(cell, dir).unwrap()))
+            .count()
+            + 1
+    }
+
+    /// The lab with the cells the guard has visited, and the guard at `cell`
+    /// facing `dir`
+    fn draw(&self, visited: &BitSet, cell: usize, dir: Direction) -> String {
+        let mut res = String::with_capacity((self.cols + 1) * self.rows);
+        for i in 0..self.rows * self.cols {
+            res.push(if i == cell {
+                match dir {
+                    Direction::North => '^',
+                    Direction::South => 'v',
+                    Direction::East => '>',
+                    Direction::West => '<',
+                }
+            } else if self.blocked.contains(i) {
+                '#'
+            } else if visited.contains(i) {
+                'X'
+            } else {
+                '.'
+            });
+            if i % self.cols == self.cols - 1 {
+                res.push('\n');
+            }
+        }
+
+        res
+    }
+
+    /// The lab drawn at the start of the guard's route, then whenever they
+    /// turn or leave
+    pub fn frames(&self) -> Vec<String> {
+        let mut visited = BitSet::new(self.rows * self.cols);
+        visited.insert(self.start);
+        let mut res = vec![self.draw(&visited, self.start, Direction::North)];
+
+        let route = self.route();
+        for (i, &(cell, dir)) in route.iter().enumerate() {
+            let next = self.step(cell, dir).unwrap();
+            visited.insert(next);
+            if route
+                .get(i + 1)
+                .is_none_or(|&(_, next_dir)| next_dir != dir)
+            {
+                res.push(self.draw(&visited, next, dir));
+            }
+        }
+
+        res
+    }
+
+    /// Whether the guard walks in a loop from `cell` in `dir` with an extra
+    /// obstruction, using `seen` for the stops they've made, which is left empty
+    fn loops(
+        &self,
+        mut cell: usize,
+        mut dir: Direction,
+        obstruction: usize,
+        seen: &mut BitSet,
+        jumps: &mut usize,
+    ) -> bool {
+        let mut stops = Vec::new();
+        let mut is_loop = false;
+        while let Some(stop) = self.jump(cell, dir, obstruction) {
+            *jumps += 1;
+            let state = stop * 4 + dir.idx();
+            if !seen.insert(state) {
+                is_loop = true;
+                break;
+            }
+            stops.push(state);
+            (cell, dir) = (stop, dir.rotate());
+        }
+
+        for state in stops {
+            seen.remove(state);
+        }
+        is_loop
+    }
+
+    /// Number of cells where a new obstruction would trap the guard in a loop
+    pub fn loop_obstructions(&self, progress: &Progress) -> Result<usize> {
+        let mut visited = BitSet::new(self.rows * self.cols);
+        visited.insert(self.start);
+        let mut seen = BitSet::new(self.rows * self.cols * 4);
+        let (mut found, mut candidates, mut jumps) = (0, 0, 0);
+
+        // the route up to a cell's first visit doesn't pass through it, so is
+        // the same with an obstruction there
+        for (cell, dir) in self.route() {
+            let next = self.step(cell, dir).unwrap();
+            if !visited.insert(next) {
+                continue;
+            }
+            candidates += 1;
+            progress.inc(1)?;
+            let _candidate = debug_span!("candidate", next).entered();
+            if self.loops(cell, dir, next, &mut seen, &mut jumps) {
+                found += 1;
+            }
+        }
+
+        info!(candidates, jumps, "candidate search finished");
+        Ok(found)
+    }
+}
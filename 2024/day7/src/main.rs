@@ -1,15 +1,13 @@
 use std::io::stdin;
 
 use anyhow::Result;
+use aoc_common::{parsers::num, CommonArgs};
 use clap::{Parser, Subcommand};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    error::Error,
-    multi::separated_list1,
-    Err, Finish, IResult,
+    bytes::complete::tag, character::complete::space1, error::Error, multi::separated_list1, Err,
+    Finish, IResult,
 };
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -21,6 +19,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 struct Equation {
@@ -28,14 +28,10 @@ struct Equation {
     operands: Vec<u64>,
 }
 
-fn number(s: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse)(s)
-}
-
 fn equation(s: &str) -> IResult<&str, Equation> {
-    let (s, test_value) = number(s)?;
+    let (s, test_value) = num(s)?;
     let (s, _) = tag(": ")(s)?;
-    let (s, operands) = separated_list1(space1, number)(s)?;
+    let (s, operands) = separated_list1(space1, num)(s)?;
 
     Ok((
         s,
@@ -79,7 +75,9 @@ fn can_make(target: u64, operands: &[u64], use_concatenation: bool) -> bool {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     let use_concatenation = matches!(args.part, Part::P2);
     let mut sum = 0;
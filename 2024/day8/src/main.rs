@@ -4,8 +4,10 @@ use std::{
 };
 
 use anyhow::{anyhow, Result};
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
 use itertools::Itertools;
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -17,6 +19,8 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type Position = (i64, i64);
@@ -121,8 +125,10 @@ fn is_in_bounds(rows: usize, cols: usize, pos: &Position) -> bool {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let map = parse_map(stdin().lock())?;
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let map = info_span!("parse").in_scope(|| parse_map(stdin().lock()))?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
     let by_freq = by_freq(&map);
 
     let antinodes = match args.part {
@@ -1,18 +1,24 @@
 use std::{cmp::Ordering, collections::VecDeque, io::stdin, iter};
 
 use anyhow::Result;
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
     P1,
     P2,
+    /// Check the input against the assumptions the solvers make
+    Lint,
 }
 
 #[derive(Parser, Debug)]
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 type BlockId = usize;
@@ -23,6 +29,13 @@ struct Run {
     len: usize,
 }
 
+fn assumption_violations(s: &str) -> Vec<String> {
+    s.char_indices()
+        .filter(|(_, ch)| !ch.is_ascii_digit())
+        .map(|(i, ch)| format!("run length at offset {i} is {ch:?}, not a single digit"))
+        .collect()
+}
+
 fn parse_input(s: &str) -> VecDeque<Run> {
     s.char_indices()
         .map(|(i, ch)| {
@@ -143,13 +156,18 @@ fn checksum(it: impl Iterator<Item = Run>) -> usize {
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
 
-    let runs = parse_input(&stdin().lines().next().expect("Unexpected empty input")?);
+    let line = stdin().lines().next().expect("Unexpected empty input")?;
+    aoc_common::check_assumptions(assumption_violations(&line))?;
+    let runs = info_span!("parse").in_scope(|| parse_input(&line));
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     match args.part {
         Part::P1 => println!("{}", checksum(p1_compact(runs))),
         Part::P2 => println!("{}", checksum(p2_compact(runs))),
+        Part::Lint => println!("All input assumptions hold"),
     }
 
     Ok(())
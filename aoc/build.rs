@@ -0,0 +1,105 @@
+//! Registers every solver package in a `<year>/day<day>` directory of the
+//! workspace, so adding a day or year needs no changes to the runner
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Optional subcommands a solver declares under `[package.metadata.aoc]`
+const FEATURES: [&str; 3] = ["lint", "explain", "animate"];
+
+struct Day {
+    year: u16,
+    day: u32,
+    /// Whether the solver has each of `FEATURES`
+    features: [bool; FEATURES.len()],
+}
+
+fn features(manifest: &Path) -> [bool; FEATURES.len()] {
+    let manifest = fs::read_to_string(manifest).unwrap();
+    let manifest: toml::Table = manifest.parse().unwrap();
+    let aoc = manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("aoc"));
+
+    FEATURES.map(|feature| {
+        aoc.and_then(|aoc| aoc.get(feature))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false)
+    })
+}
+
+fn find_days(workspace: &Path) -> Vec<Day> {
+    let mut res = Vec::new();
+    for year_dir in fs::read_dir(workspace).unwrap() {
+        let year_dir = year_dir.unwrap().path();
+        let Some(year) = year_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.parse().ok())
+        else {
+            continue;
+        };
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        for day_dir in fs::read_dir(&year_dir).unwrap() {
+            let day_dir = day_dir.unwrap().path();
+            let manifest = day_dir.join("Cargo.toml");
+            let Some(day) = day_dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("day"))
+                .and_then(|n| n.parse().ok())
+                .filter(|_| manifest.exists())
+            else {
+                continue;
+            };
+            println!("cargo:rerun-if-changed={}", manifest.display());
+
+            res.push(Day {
+                year,
+                day,
+                features: features(&manifest),
+            });
+        }
+    }
+    res.sort_unstable_by_key(|d| (d.year, d.day));
+
+    res
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let days = find_days(manifest_dir.parent().unwrap());
+
+    let mut registry = String::from("pub const YEARS: &[Year] = &[\n");
+    for year_days in days.chunk_by(|a, b| a.year == b.year) {
+        let year = year_days[0].year;
+        writeln!(
+            registry,
+            "    Year {{\n        year: {year},\n        days: &["
+        )
+        .unwrap();
+        for d in year_days {
+            write!(
+                registry,
+                "            Day {{ year: {}, day: {}",
+                d.year, d.day
+            )
+            .unwrap();
+            for (feature, has) in FEATURES.iter().zip(d.features) {
+                write!(registry, ", {feature}: {has}").unwrap();
+            }
+            writeln!(registry, " }},").unwrap();
+        }
+        writeln!(registry, "        ],\n    }},").unwrap();
+    }
+    registry.push_str("];\n");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, registry).unwrap();
+}
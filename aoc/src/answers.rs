@@ -0,0 +1,78 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    days::{Day, Part},
+    runner::Outcome,
+};
+
+/// File in the inputs directory holding the store
+const FILE: &str = "answers.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Answer {
+    /// Last line the solver printed, if it succeeded
+    pub answer: Option<String>,
+    pub succeeded: bool,
+    pub elapsed_ms: f64,
+}
+
+/// The last answer to each part run by any command, keyed like "2024 day6 p2"
+pub struct Store {
+    path: PathBuf,
+    answers: BTreeMap<String, Answer>,
+}
+
+fn key(day: &Day, part: Part) -> String {
+    format!("{day} {}", part.subcommand())
+}
+
+impl Store {
+    pub fn open(inputs: &Path) -> Result<Self> {
+        let path = inputs.join(FILE);
+        let answers = if path.exists() {
+            let answers = fs::read_to_string(&path)
+                .with_context(|| format!("Reading answers {}", path.display()))?;
+            serde_json::from_str(&answers)
+                .with_context(|| format!("Parsing answers {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self { path, answers })
+    }
+
+    pub fn get(&self, day: &Day, part: Part) -> Option<&Answer> {
+        self.answers.get(&key(day, part))
+    }
+
+    /// Record a run of a part, taking `elapsed`, which may differ from the
+    /// outcome's time if it's the median of several runs
+    pub fn record(&mut self, day: &Day, part: Part, outcome: &Outcome, elapsed: Duration) {
+        let answer = Answer {
+            answer: outcome
+                .succeeded()
+                .then(|| outcome.answer().map(str::to_string))
+                .flatten(),
+            succeeded: outcome.succeeded(),
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        };
+        self.answers.insert(key(day, part), answer);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let answers = serde_json::to_string_pretty(&self.answers)?;
+        fs::write(&self.path, answers + "\n")
+            .with_context(|| format!("Writing answers {}", self.path.display()))
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt::{self, Display};
+
+use clap::ValueEnum;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u32,
+    /// Whether the solver can check its input with a `lint` subcommand
+    pub lint: bool,
+    /// Whether the solver can say why its answer is what it is with an `explain` subcommand
+    pub explain: bool,
+    /// Whether the solver can print frames of its simulation with an `animate` subcommand
+    pub animate: bool,
+}
+
+impl Day {
+    /// Solver packages are named `aoc<year>-day<day>` so years can share a workspace
+    pub fn binary(&self) -> String {
+        format!("aoc{}-day{}", self.year, self.day)
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day{}", self.year, self.day)
+    }
+}
+
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+}
+
+// every `<year>/day<day>` package in the workspace, found by the build script
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub fn find_year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+pub fn find(year: u16, day: u32) -> Option<&'static Day> {
+    find_year(year)?.days.iter().find(|d| d.day == day)
+}
+
+pub fn latest_year() -> &'static Year {
+    YEARS
+        .iter()
+        .max_by_key(|y| y.year)
+        .expect("No years registered")
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Part {
+    #[value(alias = "1")]
+    P1,
+    #[value(alias = "2")]
+    P2,
+}
+
+impl Part {
+    pub const fn subcommand(self) -> &'static str {
+        match self {
+            Self::P1 => "p1",
+            Self::P2 => "p2",
+        }
+    }
+}
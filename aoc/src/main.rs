@@ -0,0 +1,246 @@
+mod answers;
+mod days;
+mod perf;
+mod runner;
+mod serve;
+mod tui;
+
+use std::{ffi::OsString, fs, io::Write, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use days::{Day, Part};
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List registered years and days
+    List,
+    /// Run one part of a day's solver
+    Run {
+        year: u16,
+        day: u32,
+        part: Part,
+        /// Input file (default: <inputs>/<year>/day<day>.txt)
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// TOML file of solver parameters, passed to the solver's `--config`
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Further flags for the solver, e.g. `-- --timeout 10`
+        #[arg(last = true)]
+        solver_args: Vec<OsString>,
+    },
+    /// Check inputs against the assumptions their solvers make
+    Lint {
+        /// Year to check (default: all years)
+        year: Option<u16>,
+        /// Days to check (default: all days with checks)
+        days: Vec<u32>,
+    },
+    /// Compare solve times against a recorded baseline
+    Perf {
+        #[command(subcommand)]
+        command: PerfCommand,
+    },
+    /// Browse days and run solvers interactively
+    Tui,
+    /// Serve solvers over HTTP at POST /solve/{year}/{day}/{part}, with the input as the body
+    Serve {
+        #[arg(long, default_value_t = 8024)]
+        port: u16,
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Reject inputs larger than this many bytes
+        #[arg(long, default_value_t = 1 << 20)]
+        max_input_bytes: u64,
+        /// Kill solvers which run longer than this many seconds
+        #[arg(long, default_value_t = 30.0)]
+        timeout: f64,
+        /// Turn requests away while this many are being solved
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        max_concurrent: u32,
+        /// Let web pages from this origin call the server, e.g. http://localhost:8000
+        #[arg(long)]
+        allow_origin: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PerfCommand {
+    /// Record median solve times in the baseline
+    Record {
+        #[command(flatten)]
+        opts: perf::Options,
+    },
+    /// Fail if any median solve time has regressed from the baseline
+    Check {
+        #[command(flatten)]
+        opts: perf::Options,
+        /// Percentage slowdown which counts as a regression
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Directory containing inputs named <year>/day<day>.txt
+    #[arg(long, default_value = "inputs")]
+    inputs: PathBuf,
+}
+
+fn find(year: u16, day: u32) -> Result<&'static Day> {
+    days::find(year, day).with_context(|| format!("Unknown day {day} in {year}"))
+}
+
+fn list() {
+    for year in days::YEARS {
+        let days = year
+            .days
+            .iter()
+            .map(|d| d.day.to_string())
+            .collect::<Vec<_>>();
+        println!("{}: {}", year.year, days.join(" "));
+    }
+}
+
+fn run(
+    args: &Args,
+    day: &Day,
+    part: Part,
+    input: Option<&PathBuf>,
+    solver_args: &[OsString],
+) -> Result<()> {
+    let input = input.map_or_else(|| runner::input_path(&args.inputs, day), PathBuf::clone);
+    let input = fs::read(&input).with_context(|| format!("Reading input {}", input.display()))?;
+
+    let outcome = runner::run(day, part.subcommand(), solver_args, input, None)?;
+    let mut answers = answers::Store::open(&args.inputs)?;
+    answers.record(day, part, &outcome, outcome.elapsed);
+    answers.save()?;
+    print!("{}", outcome.stdout);
+    eprint!("{}", outcome.stderr);
+    std::io::stdout().flush()?;
+    eprintln!("{day} {}: {:?}", part.subcommand(), outcome.elapsed);
+    if !outcome.succeeded() {
+        bail!("{day} {} failed", part.subcommand());
+    }
+
+    Ok(())
+}
+
+/// The given days of `year`, or all days of the year or of every year if unspecified
+fn select(year: Option<u16>, days: &[u32]) -> Result<Vec<&'static Day>> {
+    match year {
+        None => Ok(days::YEARS.iter().flat_map(|y| y.days).collect()),
+        Some(year) if days.is_empty() => Ok(days::find_year(year)
+            .with_context(|| format!("Unknown year {year}"))?
+            .days
+            .iter()
+            .collect()),
+        Some(year) => days.iter().map(|&day| find(year, day)).collect(),
+    }
+}
+
+fn lint(args: &Args, year: Option<u16>, days: &[u32]) -> Result<()> {
+    let mut selected = select(year, days)?;
+    if days.is_empty() {
+        selected.retain(|d| d.lint);
+    }
+
+    let mut failed = 0;
+    for day in &selected {
+        let input = runner::input_path(&args.inputs, day);
+        if !day.lint {
+            println!("{day}: no checks");
+            continue;
+        } else if !input.exists() {
+            println!("{day}: skipped, no input at {}", input.display());
+            continue;
+        }
+
+        let outcome = runner::run_file(day, "lint", &input)?;
+        if outcome.succeeded() {
+            println!("{day}: ok");
+        } else {
+            failed += 1;
+            println!("{day}: FAILED");
+            for line in outcome.stderr.lines() {
+                println!("  {line}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} input(s) violate solver assumptions");
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match &args.command {
+        Command::List => list(),
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            config,
+            solver_args,
+        } => {
+            // `--config` is global, so may follow the subcommand
+            let solver_args = config
+                .iter()
+                .flat_map(|config| [OsString::from("--config"), config.into()])
+                .chain(solver_args.iter().cloned())
+                .collect::<Vec<_>>();
+            run(
+                &args,
+                find(*year, *day)?,
+                *part,
+                input.as_ref(),
+                &solver_args,
+            )?;
+        }
+        Command::Lint { year, days } => lint(&args, *year, days)?,
+        Command::Perf { command } => match command {
+            PerfCommand::Record { opts } => {
+                perf::record(&args.inputs, &select(opts.year, &opts.days)?, opts)?;
+            }
+            PerfCommand::Check { opts, threshold } => {
+                perf::check(
+                    &args.inputs,
+                    &select(opts.year, &opts.days)?,
+                    opts,
+                    *threshold,
+                )?;
+            }
+        },
+        Command::Tui => tui::tui(&args.inputs)?,
+        Command::Serve {
+            port,
+            host,
+            max_input_bytes,
+            timeout,
+            max_concurrent,
+            allow_origin,
+        } => serve::serve(
+            host,
+            *port,
+            serve::Limits {
+                max_input_bytes: *max_input_bytes,
+                timeout: Duration::try_from_secs_f64(*timeout)?,
+                max_concurrent: *max_concurrent as usize,
+            },
+            allow_origin.as_deref(),
+        )?,
+    }
+
+    Ok(())
+}
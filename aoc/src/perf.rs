@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::{
+    answers,
+    days::{Day, Part},
+    runner,
+};
+
+/// Slowdowns smaller than this are process startup jitter, whatever the percentage
+const NOISE_FLOOR: Duration = Duration::from_millis(5);
+
+/// Median solve time in milliseconds of each part, keyed like "2024 day6 p2"
+type Baseline = BTreeMap<String, f64>;
+
+#[derive(Args, Debug)]
+pub struct Options {
+    /// Year to measure (default: all years)
+    pub year: Option<u16>,
+    /// Days to measure (default: all days)
+    pub days: Vec<u32>,
+    /// Baseline file of median solve times
+    #[arg(long, default_value = "perf-baseline.json")]
+    baseline: PathBuf,
+    /// Number of times to run each part
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+}
+
+fn read_baseline(path: &Path) -> Result<Baseline> {
+    if !path.exists() {
+        return Ok(Baseline::new());
+    }
+    let baseline =
+        fs::read_to_string(path).with_context(|| format!("Reading baseline {}", path.display()))?;
+    serde_json::from_str(&baseline).with_context(|| format!("Parsing baseline {}", path.display()))
+}
+
+/// Run every part of every selected day with an input, yielding median solve
+/// times, which are also recorded in the answer store
+fn measure(
+    inputs: &Path,
+    days: &[&Day],
+    runs: u32,
+    mut report: impl FnMut(String, Duration),
+) -> Result<()> {
+    let mut answers = answers::Store::open(inputs)?;
+    for day in days {
+        let input = runner::input_path(inputs, day);
+        if !input.exists() {
+            println!("{day}: skipped, no input at {}", input.display());
+            continue;
+        }
+
+        for part in [Part::P1, Part::P2] {
+            let mut times = Vec::new();
+            let mut last = None;
+            for _ in 0..runs {
+                let outcome = runner::run_file(day, part.subcommand(), &input)?;
+                if !outcome.succeeded() {
+                    bail!(
+                        "{day} {} failed:\n{}",
+                        part.subcommand(),
+                        outcome.stderr.trim_end()
+                    );
+                }
+                times.push(outcome.elapsed);
+                last = Some(outcome);
+            }
+            times.sort_unstable();
+            let median = times[times.len() / 2];
+            if let Some(outcome) = &last {
+                answers.record(day, part, outcome, median);
+            }
+            report(format!("{day} {}", part.subcommand()), median);
+        }
+    }
+
+    answers.save()
+}
+
+/// Measure the selected days and merge their times into the baseline
+pub fn record(inputs: &Path, days: &[&Day], opts: &Options) -> Result<()> {
+    let mut baseline = read_baseline(&opts.baseline)?;
+    measure(inputs, days, opts.runs, |key, median| {
+        println!("{key}: {median:.1?}");
+        baseline.insert(key, median.as_secs_f64() * 1000.0);
+    })?;
+
+    let baseline = serde_json::to_string_pretty(&baseline)?;
+    fs::write(&opts.baseline, baseline + "\n")
+        .with_context(|| format!("Writing baseline {}", opts.baseline.display()))?;
+
+    Ok(())
+}
+
+/// Measure the selected days, failing if any is more than `threshold` percent
+/// slower than its baseline
+pub fn check(inputs: &Path, days: &[&Day], opts: &Options, threshold: f64) -> Result<()> {
+    let baseline = read_baseline(&opts.baseline)?;
+    let mut regressed = 0;
+    measure(inputs, days, opts.runs, |key, median| {
+        let Some(&base_ms) = baseline.get(&key) else {
+            println!("{key}: {median:.1?}, no baseline");
+            return;
+        };
+        let base = Duration::from_secs_f64(base_ms / 1000.0);
+        let change = (median.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0;
+        let verdict = if change > threshold && median > base + NOISE_FLOOR {
+            regressed += 1;
+            "REGRESSED"
+        } else {
+            "ok"
+        };
+        println!("{key}: {median:.1?} vs {base:.1?} baseline ({change:+.1}%) {verdict}");
+    })?;
+
+    if regressed > 0 {
+        bail!("{regressed} part(s) regressed by more than {threshold}%");
+    }
+
+    Ok(())
+}
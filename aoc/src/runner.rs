@@ -0,0 +1,131 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+
+use crate::days::Day;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Solvers are built into the same target directory as the runner
+fn binary_path(day: &Day) -> Result<PathBuf> {
+    let exe = env::current_exe().context("Locating runner executable")?;
+    let mut dir = exe.parent().context("Runner executable has no directory")?;
+    // the runner's tests are built into `deps` below the solvers
+    if dir.ends_with("deps") {
+        dir = dir.parent().unwrap_or(dir);
+    }
+    let path = dir.join(format!("{}{}", day.binary(), env::consts::EXE_SUFFIX));
+    ensure!(
+        path.exists(),
+        "No solver binary at {}; build it with `cargo build -p {}`",
+        path.display(),
+        day.binary()
+    );
+
+    Ok(path)
+}
+
+pub fn input_path(inputs: &Path, day: &Day) -> PathBuf {
+    inputs
+        .join(day.year.to_string())
+        .join(format!("day{}.txt", day.day))
+}
+
+pub struct Outcome {
+    pub stdout: String,
+    pub stderr: String,
+    pub elapsed: Duration,
+    /// `None` if the solver was killed after timing out
+    pub status: Option<ExitStatus>,
+}
+
+impl Outcome {
+    pub fn succeeded(&self) -> bool {
+        self.status.is_some_and(|status| status.success())
+    }
+
+    /// Solvers print their answer last, after any diagnostics
+    pub fn answer(&self) -> Option<&str> {
+        self.stdout
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+    }
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        // a solver killed mid-write still leaves useful partial output
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+/// Run a solver subcommand with `args` after it and `input` on stdin, killing
+/// it after `timeout`
+pub fn run(
+    day: &Day,
+    subcommand: &str,
+    args: &[OsString],
+    input: Vec<u8>,
+    timeout: Option<Duration>,
+) -> Result<Outcome> {
+    let mut child = Command::new(binary_path(day)?)
+        .arg(subcommand)
+        .args(args)
+        // solver errors are reported to users, who don't need backtraces
+        .env("RUST_LIB_BACKTRACE", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Running {}", day.binary()))?;
+
+    let start = Instant::now();
+    let mut stdin = child.stdin.take().context("Missing solver stdin")?;
+    // solvers may exit without reading all of their input
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let stdout = read_in_background(child.stdout.take().context("Missing solver stdout")?);
+    let stderr = read_in_background(child.stderr.take().context("Missing solver stderr")?);
+
+    let status = match timeout {
+        None => Some(child.wait()?),
+        Some(timeout) => loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            } else if start.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                break None;
+            }
+            thread::sleep(POLL_INTERVAL);
+        },
+    };
+    let elapsed = start.elapsed();
+
+    let _ = writer.join();
+    let join_err = |_| anyhow!("Output reader panicked");
+    Ok(Outcome {
+        stdout: stdout.join().map_err(join_err)?,
+        stderr: stderr.join().map_err(join_err)?,
+        elapsed,
+        status,
+    })
+}
+
+/// Run a solver subcommand with the given input file on stdin
+pub fn run_file(day: &Day, subcommand: &str, input: &Path) -> Result<Outcome> {
+    let input = fs::read(input).with_context(|| format!("Reading input {}", input.display()))?;
+
+    run(day, subcommand, &[], input, None)
+}
@@ -0,0 +1,250 @@
+use std::{
+    io::Read,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+use crate::{
+    days::{self, Part},
+    runner,
+};
+
+#[derive(Debug, Clone)]
+pub struct Limits {
+    pub max_input_bytes: u64,
+    pub timeout: Duration,
+    /// Most requests solved at once, beyond which requests are turned away
+    pub max_concurrent: usize,
+}
+
+#[derive(Serialize)]
+struct Solution {
+    answer: Option<String>,
+    output: String,
+    elapsed_ms: f64,
+    error: Option<String>,
+}
+
+struct Rejection {
+    status: u16,
+    message: String,
+}
+
+fn reject(status: u16, message: impl Into<String>) -> Rejection {
+    Rejection {
+        status,
+        message: message.into(),
+    }
+}
+
+/// Parse `/solve/{year}/{day}/{part}`, or `/solve/{day}/{part}` for the latest
+/// year, accepting parts as `1` or `p1`
+fn route(url: &str) -> Result<(&'static days::Day, Part), Rejection> {
+    let path = url.split_once('?').map_or(url, |(path, _)| path);
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (year, day, part) = match segments[..] {
+        ["solve", year, day, part] => {
+            let year = year
+                .parse()
+                .ok()
+                .and_then(days::find_year)
+                .ok_or_else(|| reject(404, format!("Unknown year {year}")))?;
+            (year, day, part)
+        }
+        ["solve", day, part] => (days::latest_year(), day, part),
+        _ => return Err(reject(404, "Expected POST /solve/{year}/{day}/{part}")),
+    };
+
+    let day = day
+        .parse()
+        .ok()
+        .and_then(|day| days::find(year.year, day))
+        .ok_or_else(|| reject(404, format!("Unknown day {day} in {}", year.year)))?;
+    let part =
+        Part::from_str(part, true).map_err(|_| reject(404, format!("Unknown part {part}")))?;
+
+    Ok((day, part))
+}
+
+fn solve(request: &mut Request, limits: &Limits) -> Result<(u16, Solution), Rejection> {
+    let (day, part) = route(request.url())?;
+    if *request.method() != Method::Post {
+        return Err(reject(405, "Only POST is supported"));
+    }
+
+    let too_large = || {
+        reject(
+            413,
+            format!("Input exceeds {} bytes", limits.max_input_bytes),
+        )
+    };
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > limits.max_input_bytes)
+    {
+        return Err(too_large());
+    }
+    let mut input = Vec::new();
+    request
+        .as_reader()
+        .take(limits.max_input_bytes + 1)
+        .read_to_end(&mut input)
+        .map_err(|e| reject(400, format!("Failed to read body: {e}")))?;
+    if input.len() as u64 > limits.max_input_bytes {
+        return Err(too_large());
+    }
+
+    let outcome = runner::run(day, part.subcommand(), &[], input, Some(limits.timeout))
+        .map_err(|e| reject(500, format!("{e:#}")))?;
+    let (status, error) = if outcome.status.is_none() {
+        (504, Some(format!("Timed out after {:?}", limits.timeout)))
+    } else if !outcome.succeeded() {
+        (422, Some(outcome.stderr.clone()))
+    } else {
+        (200, None)
+    };
+
+    Ok((
+        status,
+        Solution {
+            answer: outcome.answer().map(str::to_string),
+            elapsed_ms: outcome.elapsed.as_secs_f64() * 1000.0,
+            output: outcome.stdout,
+            error,
+        },
+    ))
+}
+
+fn respond(request: Request, status: u16, body: &str, allow_origin: Option<&str>) {
+    let mut response = Response::from_string(body)
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Some(origin) = allow_origin {
+        response.add_header(Header::from_bytes("Access-Control-Allow-Origin", origin).unwrap());
+    }
+    // the client may have hung up while we were solving
+    let _ = request.respond(response);
+}
+
+fn error_body(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
+
+fn handle(mut request: Request, limits: &Limits, allow_origin: Option<&str>) {
+    let (status, body) = match solve(&mut request, limits) {
+        Ok((status, solution)) => (
+            status,
+            serde_json::to_string(&solution).expect("Solutions always serialize"),
+        ),
+        Err(rejection) => (rejection.status, error_body(&rejection.message)),
+    };
+    respond(request, status, &body, allow_origin);
+}
+
+/// Counts a request as being solved until dropped
+struct InFlight(Arc<AtomicUsize>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Solve requests to `server` until it shuts down, each on its own thread
+fn run(server: &Server, limits: &Limits, allow_origin: Option<&str>) {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    for request in server.incoming_requests() {
+        // each request runs a solver process, so don't let clients start
+        // arbitrarily many
+        if in_flight.load(Ordering::SeqCst) >= limits.max_concurrent {
+            respond(
+                request,
+                503,
+                &error_body("Too many requests in progress, try again later"),
+                allow_origin,
+            );
+            continue;
+        }
+
+        in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight(Arc::clone(&in_flight));
+        let limits = limits.clone();
+        let allow_origin = allow_origin.map(str::to_string);
+        thread::spawn(move || {
+            handle(request, &limits, allow_origin.as_deref());
+            drop(guard);
+        });
+    }
+}
+
+/// Serve solvers on `host:port`, letting web pages from `allow_origin` call
+/// them if given
+pub fn serve(host: &str, port: u16, limits: Limits, allow_origin: Option<&str>) -> Result<()> {
+    let server = Server::http((host, port))
+        .map_err(|e| anyhow!("Failed to listen on {host}:{port}: {e}"))?;
+    eprintln!("Listening on http://{}", server.server_addr());
+    run(&server, &limits, allow_origin);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        process::Command,
+        thread,
+        time::Duration,
+    };
+
+    use tiny_http::Server;
+
+    use super::{run, Limits};
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn solves_example_on_localhost() {
+        // the server runs solvers from the target directory, so make sure
+        // this one is there
+        let built = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "-p", "aoc2024-day1"])
+            .status()
+            .unwrap();
+        assert!(built.success());
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let limits = Limits {
+            max_input_bytes: 1 << 10,
+            timeout: Duration::from_secs(30),
+            max_concurrent: 1,
+        };
+        thread::spawn(move || run(&server, &limits, None));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /solve/2024/1/p1 HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{EXAMPLE}",
+            EXAMPLE.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""answer":"11""#), "{response}");
+        assert!(!response.contains("Access-Control-Allow-Origin"));
+    }
+}
@@ -0,0 +1,441 @@
+use std::{
+    fs,
+    path::Path,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    answers::{self, Answer},
+    days::{self, Day, Part},
+    runner::{self, Outcome},
+};
+
+const TICK: Duration = Duration::from_millis(50);
+/// Time each frame of a playing animation is shown for
+const FRAME_TIME: Duration = Duration::from_millis(250);
+
+/// Solver subcommands the TUI can run
+#[derive(Debug, Clone, Copy)]
+enum Job {
+    Part(Part),
+    Explain,
+    Animate,
+}
+
+impl Job {
+    const fn subcommand(self) -> &'static str {
+        match self {
+            Self::Part(part) => part.subcommand(),
+            Self::Explain => "explain",
+            Self::Animate => "animate",
+        }
+    }
+}
+
+type Finished = (usize, Job, Result<Outcome>);
+
+enum JobState {
+    NotRun,
+    /// Answer recorded by an earlier run, of any command
+    Stored(Answer),
+    Running,
+    Failed(String),
+    Done(Outcome),
+}
+
+struct Entry {
+    day: &'static Day,
+    parts: [JobState; 2],
+    explain: JobState,
+    animate: JobState,
+    /// Part whose output is shown in the panes
+    shown: Option<Part>,
+}
+
+impl Entry {
+    const fn state_mut(&mut self, job: Job) -> &mut JobState {
+        match job {
+            Job::Part(part) => &mut self.parts[part_idx(part)],
+            Job::Explain => &mut self.explain,
+            Job::Animate => &mut self.animate,
+        }
+    }
+}
+
+/// Frames of a day's `animate` output being played back
+struct Animation {
+    title: String,
+    frames: Vec<String>,
+    frame: usize,
+    playing: bool,
+    shown_at: Instant,
+}
+
+impl Animation {
+    fn step(&mut self, forward: bool) {
+        self.frame = if forward {
+            (self.frame + 1).min(self.frames.len() - 1)
+        } else {
+            self.frame.saturating_sub(1)
+        };
+        self.shown_at = Instant::now();
+    }
+
+    fn tick(&mut self) {
+        if self.playing && self.shown_at.elapsed() >= FRAME_TIME {
+            self.step(true);
+            // stop at the last frame rather than looping
+            self.playing = self.frame + 1 < self.frames.len();
+        }
+    }
+}
+
+struct App<'a> {
+    inputs: &'a Path,
+    answers: answers::Store,
+    entries: Vec<Entry>,
+    list: ListState,
+    scroll: u16,
+    /// Shown instead of the days while set
+    animation: Option<Animation>,
+    tx: Sender<Finished>,
+    rx: Receiver<Finished>,
+}
+
+const fn part_idx(part: Part) -> usize {
+    match part {
+        Part::P1 => 0,
+        Part::P2 => 1,
+    }
+}
+
+fn part_summary(state: &JobState) -> Span<'_> {
+    match state {
+        JobState::NotRun => Span::styled("-", Style::new().fg(Color::DarkGray)),
+        JobState::Stored(answer) if !answer.succeeded => {
+            Span::styled("failed", Style::new().fg(Color::DarkGray))
+        }
+        JobState::Stored(answer) => Span::styled(
+            format!(
+                "{} ({:.1?})",
+                answer.answer.as_deref().unwrap_or_default(),
+                Duration::from_secs_f64(answer.elapsed_ms / 1000.0)
+            ),
+            Style::new().fg(Color::DarkGray),
+        ),
+        JobState::Running => Span::styled("running", Style::new().fg(Color::Yellow)),
+        JobState::Failed(_) => Span::styled("error", Style::new().fg(Color::Red)),
+        JobState::Done(outcome) if !outcome.succeeded() => {
+            Span::styled("failed", Style::new().fg(Color::Red))
+        }
+        JobState::Done(outcome) => Span::styled(
+            format!(
+                "{} ({:.1?})",
+                outcome.answer().unwrap_or_default(),
+                outcome.elapsed
+            ),
+            Style::new().fg(Color::Green),
+        ),
+    }
+}
+
+/// Output of a job for the panes, as (stdout, stderr)
+fn job_output(state: &JobState) -> (&str, &str) {
+    match state {
+        JobState::Done(outcome) => (&outcome.stdout, &outcome.stderr),
+        JobState::Failed(e) => ("", e),
+        JobState::NotRun | JobState::Stored(_) | JobState::Running => ("", ""),
+    }
+}
+
+impl App<'_> {
+    fn selected(&self) -> usize {
+        self.list.selected().unwrap_or_default()
+    }
+
+    fn start(&mut self, job: Job) {
+        let idx = self.selected();
+        let entry = &mut self.entries[idx];
+        let supported = match job {
+            Job::Part(_) => true,
+            Job::Explain => entry.day.explain,
+            Job::Animate => entry.day.animate,
+        };
+        let state = entry.state_mut(job);
+        if !supported || matches!(state, JobState::Running) {
+            return;
+        }
+        *state = JobState::Running;
+        if let Job::Part(part) = job {
+            entry.shown = Some(part);
+            self.scroll = 0;
+        }
+
+        let day = entry.day;
+        let input = runner::input_path(self.inputs, day);
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            let res = fs::read(&input)
+                .with_context(|| format!("Reading input {}", input.display()))
+                .and_then(|input| runner::run(day, job.subcommand(), &[], input, None));
+            // the receiver is gone if the user quit mid-run
+            let _ = tx.send((idx, job, res));
+        });
+    }
+
+    fn receive(&mut self) -> Result<()> {
+        while let Ok((idx, job, res)) = self.rx.try_recv() {
+            let entry = &mut self.entries[idx];
+            if let (Job::Part(part), Ok(outcome)) = (job, &res) {
+                self.answers
+                    .record(entry.day, part, outcome, outcome.elapsed);
+                self.answers.save()?;
+            }
+            if let (Job::Animate, Ok(outcome)) = (job, &res) {
+                let frames = aoc_common::animation::frames(&outcome.stdout);
+                // a failed run is left in the animate pane to be read
+                if outcome.succeeded() && !frames.is_empty() {
+                    self.animation = Some(Animation {
+                        title: format!("{} animate", entry.day),
+                        frames,
+                        frame: 0,
+                        playing: true,
+                        shown_at: Instant::now(),
+                    });
+                }
+            }
+            *entry.state_mut(job) = match res {
+                Ok(outcome) => JobState::Done(outcome),
+                Err(e) => JobState::Failed(format!("{e:#}")),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn draw_animation(animation: &Animation, frame: &mut Frame) {
+        let [frame_area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let title = format!(
+            "{} - frame {}/{}{}",
+            animation.title,
+            animation.frame + 1,
+            animation.frames.len(),
+            if animation.playing { " (playing)" } else { "" }
+        );
+        frame.render_widget(
+            Paragraph::new(&*animation.frames[animation.frame])
+                .block(Block::bordered().title(title)),
+            frame_area,
+        );
+        frame.render_widget(
+            Paragraph::new("←/→ step  space play/pause  Home/End first/last  Esc back"),
+            help_area,
+        );
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        if let Some(animation) = &self.animation {
+            Self::draw_animation(animation, frame);
+            return;
+        }
+
+        let [days_area, output_area] =
+            Layout::horizontal([Constraint::Length(48), Constraint::Fill(1)]).areas(frame.area());
+        let entry = &self.entries[self.selected()];
+        // the explain pane is only shown once there's something to put in it
+        let explain_shown = !matches!(entry.explain, JobState::NotRun);
+        let [stdout_area, explain_area, stderr_area, help_area] = Layout::vertical([
+            Constraint::Fill(3),
+            Constraint::Fill(if explain_shown { 3 } else { 0 }),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(output_area);
+
+        let items = self.entries.iter().map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<11}", entry.day.to_string())),
+                part_summary(&entry.parts[0]),
+                Span::raw("  "),
+                part_summary(&entry.parts[1]),
+            ]))
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title("Days"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, days_area, &mut self.list);
+
+        let (title, stdout, mut stderr) = match entry.shown {
+            None => ("Output".to_string(), "", ""),
+            Some(part) => {
+                let (stdout, stderr) = job_output(&entry.parts[part_idx(part)]);
+                (
+                    format!("{} {}", entry.day, part.subcommand()),
+                    stdout,
+                    stderr,
+                )
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(stdout)
+                .scroll((self.scroll, 0))
+                .block(Block::bordered().title(title)),
+            stdout_area,
+        );
+        if explain_shown {
+            let explain = match &entry.explain {
+                JobState::Running => "running",
+                state => job_output(state).0,
+            };
+            frame.render_widget(
+                Paragraph::new(explain)
+                    .scroll((self.scroll, 0))
+                    .block(Block::bordered().title(format!("{} explain", entry.day))),
+                explain_area,
+            );
+        }
+        // explain and animate errors have nowhere else to go
+        for state in [&entry.explain, &entry.animate] {
+            let failed = match state {
+                JobState::Failed(_) => true,
+                JobState::Done(outcome) => !outcome.succeeded(),
+                _ => false,
+            };
+            if failed {
+                stderr = job_output(state).1;
+            }
+        }
+        frame.render_widget(
+            Paragraph::new(stderr).block(Block::bordered().title("stderr")),
+            stderr_area,
+        );
+        frame.render_widget(
+            Paragraph::new(
+                "↑/↓ select  1/2 run part  e explain  a animate  PgUp/PgDn scroll  q quit",
+            ),
+            help_area,
+        );
+    }
+
+    /// Handle a key while an animation is shown
+    fn animation_key(&mut self, code: KeyCode) {
+        let Some(animation) = &mut self.animation else {
+            return;
+        };
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.animation = None,
+            KeyCode::Left | KeyCode::Char('h') => {
+                animation.playing = false;
+                animation.step(false);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                animation.playing = false;
+                animation.step(true);
+            }
+            KeyCode::Home => animation.frame = 0,
+            KeyCode::End => animation.frame = animation.frames.len() - 1,
+            KeyCode::Char(' ') => {
+                if animation.frame + 1 == animation.frames.len() {
+                    animation.frame = 0;
+                }
+                animation.playing = !animation.playing;
+                animation.shown_at = Instant::now();
+            }
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            self.receive()?;
+            if let Some(animation) = &mut self.animation {
+                animation.tick();
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if self.animation.is_some() {
+                self.animation_key(key.code);
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.list.select_previous();
+                    self.scroll = 0;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.list.select_next();
+                    self.scroll = 0;
+                }
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::Char('1') => self.start(Job::Part(Part::P1)),
+                KeyCode::Char('2') => self.start(Job::Part(Part::P2)),
+                KeyCode::Char('e') => self.start(Job::Explain),
+                KeyCode::Char('a') => self.start(Job::Animate),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn tui(inputs: &Path) -> Result<()> {
+    let answers = answers::Store::open(inputs)?;
+    let stored = |day, part| {
+        answers
+            .get(day, part)
+            .map_or(JobState::NotRun, |answer| JobState::Stored(answer.clone()))
+    };
+    let entries = days::YEARS
+        .iter()
+        .flat_map(|year| year.days)
+        .map(|day| Entry {
+            day,
+            parts: [stored(day, Part::P1), stored(day, Part::P2)],
+            explain: JobState::NotRun,
+            animate: JobState::NotRun,
+            shown: None,
+        })
+        .collect();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App {
+        inputs,
+        answers,
+        entries,
+        list: ListState::default().with_selected(Some(0)),
+        scroll: 0,
+        animation: None,
+        tx,
+        rx,
+    };
+
+    let mut terminal = ratatui::init();
+    let res = app.run(&mut terminal);
+    ratatui::restore();
+
+    res
+}
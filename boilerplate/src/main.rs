@@ -1,5 +1,7 @@
 use anyhow::Result;
+use aoc_common::CommonArgs;
 use clap::{Parser, Subcommand};
+use tracing::info_span;
 
 #[derive(Debug, Subcommand)]
 enum Part {
@@ -11,10 +13,14 @@ enum Part {
 struct Args {
     #[command(subcommand)]
     part: Part,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = aoc_common::parse::<Args>()?;
+    let _session = args.common.init()?;
+    let _solve = info_span!("solve", part = ?args.part).entered();
 
     match args.part {
         Part::P1 => todo!(),
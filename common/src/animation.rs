@@ -0,0 +1,24 @@
+//! Frames printed by solvers' `animate` subcommands, for the runner to play back
+
+/// Printed on a line of its own after each frame
+pub const FRAME_END: &str = "\x0c";
+
+/// Print one frame of an animation
+pub fn print_frame(frame: &str) {
+    print!("{frame}");
+    if !frame.ends_with('\n') {
+        println!();
+    }
+    println!("{FRAME_END}");
+}
+
+/// Split the output of an `animate` subcommand back into frames
+pub fn frames(output: &str) -> Vec<String> {
+    output
+        .split_inclusive('\n')
+        .collect::<Vec<_>>()
+        .split(|line| line.trim_end_matches(['\r', '\n']) == FRAME_END)
+        .map(|lines| lines.concat())
+        .filter(|frame| !frame.is_empty())
+        .collect()
+}
@@ -0,0 +1,75 @@
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use toml::{Table, Value};
+
+/// Parse solver arguments from the command line, filling in parameters from the
+/// TOML file given by `--config`.
+///
+/// Each `key = value` in the file becomes a `--key=value` flag placed ahead of
+/// the command line, so flags given explicitly take precedence over the file.
+/// Underscores in keys become dashes, so `max_step` sets `--max-step`.
+///
+/// Since the flags come before the subcommand, only the solver's own
+/// parameters can be configured, not those of a subcommand.
+pub fn parse<T: Parser>() -> Result<T> {
+    let mut args = env::args_os();
+    let bin = args.next().unwrap_or_default();
+    let args = args.collect::<Vec<_>>();
+
+    let flags = match config_path(&args) {
+        Some(path) => read_flags(&path)?,
+        None => Vec::new(),
+    };
+
+    let mut cmd = T::command().args_override_self(true);
+    let matches = cmd
+        .try_get_matches_from_mut([bin].into_iter().chain(flags).chain(args))
+        .unwrap_or_else(|e| e.exit());
+    Ok(T::from_arg_matches(&matches).unwrap_or_else(|e| e.format(&mut cmd).exit()))
+}
+
+/// Find the `--config` argument before clap sees it, since the file it names
+/// changes what the command line parses to
+fn config_path(args: &[OsString]) -> Option<PathBuf> {
+    let mut args = args.iter().map(OsString::as_os_str);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+fn read_flags(path: &Path) -> Result<Vec<OsString>> {
+    let config = fs::read_to_string(path)
+        .with_context(|| format!("Reading config {}", path.display()))?
+        .parse::<Table>()
+        .with_context(|| format!("Parsing config {}", path.display()))?;
+
+    config
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s,
+                Value::Integer(i) => i.to_string(),
+                Value::Float(f) => f.to_string(),
+                Value::Boolean(b) => b.to_string(),
+                _ => bail!("Unsupported value for {key} in config {}", path.display()),
+            };
+            let mut flag = OsString::from(format!("--{}=", key.replace('_', "-")));
+            flag.push(OsStr::new(&value));
+            Ok(flag)
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+};
+
+use anyhow::{bail, Result};
+
+/// Row and column, counting from 0
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Change in row and column of one step
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (-1, 0),
+            Self::NorthEast => (-1, 1),
+            Self::East => (0, 1),
+            Self::SouthEast => (1, 1),
+            Self::South => (1, 0),
+            Self::SouthWest => (1, -1),
+            Self::West => (0, -1),
+            Self::NorthWest => (-1, -1),
+        }
+    }
+
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::NorthEast => Self::SouthWest,
+            Self::East => Self::West,
+            Self::SouthEast => Self::NorthWest,
+            Self::South => Self::North,
+            Self::SouthWest => Self::NorthEast,
+            Self::West => Self::East,
+            Self::NorthWest => Self::SouthEast,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::North => "N",
+            Self::NorthEast => "NE",
+            Self::East => "E",
+            Self::SouthEast => "SE",
+            Self::South => "S",
+            Self::SouthWest => "SW",
+            Self::West => "W",
+            Self::NorthWest => "NW",
+        })
+    }
+}
+
+/// A rectangular grid of cells, which are characters unless parsed into
+/// something else
+pub struct Grid<T = char> {
+    pub rows: usize,
+    pub cols: usize,
+    cells: Vec<T>,
+}
+
+impl Grid {
+    /// Parse lines of equal length into a grid of their characters
+    pub fn parse(inp: impl BufRead) -> Result<Self> {
+        let mut cells = Vec::new();
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for line in inp.lines() {
+            let line = line?;
+            let len = line.chars().count();
+            if rows == 0 {
+                cols = len;
+            } else if len != cols {
+                bail!(
+                    "Line {} has length {len}, but line 1 has length {cols}",
+                    rows + 1
+                );
+            }
+            cells.extend(line.chars());
+            rows += 1;
+        }
+
+        Ok(Self { rows, cols, cells })
+    }
+
+    /// Convert each cell with `f`
+    pub fn try_map<U>(self, f: impl FnMut(char) -> Result<U>) -> Result<Grid<U>> {
+        Ok(Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.into_iter().map(f).collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn get(&self, (r, c): Position) -> T {
+        self.cells[r * self.cols + c]
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.rows).flat_map(|r| (0..self.cols).map(move |c| (r, c)))
+    }
+
+    /// The neighbour of `pos` in `dir`, or `None` at the edge unless `wrap`
+    /// connects opposite edges
+    pub fn step(&self, (r, c): Position, dir: Direction, wrap: bool) -> Option<Position> {
+        let (dr, dc) = dir.delta();
+        if wrap {
+            let r = (r as isize + dr).rem_euclid(self.rows as isize) as usize;
+            let c = (c as isize + dc).rem_euclid(self.cols as isize) as usize;
+            return Some((r, c));
+        }
+
+        let r = r.checked_add_signed(dr).filter(|&r| r < self.rows)?;
+        let c = c.checked_add_signed(dc).filter(|&c| c < self.cols)?;
+        Some((r, c))
+    }
+}
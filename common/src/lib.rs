@@ -0,0 +1,81 @@
+#[cfg(feature = "mem-profile")]
+pub mod alloc;
+pub mod animation;
+pub mod config;
+pub mod grid;
+pub mod memo;
+pub mod parsers;
+pub mod progress;
+pub mod trace;
+
+pub use config::parse;
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{ensure, Result};
+use clap::Args;
+use progress::{CancelToken, Progress};
+
+/// Options shared by every solver
+#[derive(Args, Debug)]
+#[command(about = None, long_about = None)]
+pub struct CommonArgs {
+    /// Write a Chrome trace (chrome://tracing, Perfetto) of the run to this file
+    #[arg(long, global = true)]
+    pub trace: Option<PathBuf>,
+
+    /// Read solver parameters (not subcommand ones) from this TOML file; flags given on the command line take precedence
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Give up on long searches after this many seconds
+    #[arg(long, global = true)]
+    pub timeout: Option<f64>,
+}
+
+/// Per-run state which must be kept alive until the solver finishes
+pub struct Session {
+    _trace: trace::Guard,
+    cancel: CancelToken,
+}
+
+/// With the `mem-profile` feature, report heap usage once the solver finishes
+#[cfg(feature = "mem-profile")]
+impl Drop for Session {
+    fn drop(&mut self) {
+        eprintln!("Heap: {}", alloc::stats());
+    }
+}
+
+impl Session {
+    /// Token for searches which poll for cancellation without reporting progress
+    pub const fn cancel(&self) -> CancelToken {
+        self.cancel
+    }
+
+    /// Report progress through `len` items (or an unknown number) of a long search
+    pub fn progress(&self, what: &'static str, len: Option<u64>) -> Progress {
+        Progress::new(what, len, self.cancel)
+    }
+}
+
+/// Fail with each way the input breaks the solver's assumptions, if there are any
+pub fn check_assumptions(violations: Vec<String>) -> Result<()> {
+    ensure!(
+        violations.is_empty(),
+        "Input violates solver assumptions:\n  {}",
+        violations.join("\n  ")
+    );
+
+    Ok(())
+}
+
+impl CommonArgs {
+    pub fn init(&self) -> Result<Session> {
+        let timeout = self.timeout.map(Duration::try_from_secs_f64).transpose()?;
+        Ok(Session {
+            _trace: trace::init(self.trace.as_deref())?,
+            cancel: CancelToken::new(timeout),
+        })
+    }
+}
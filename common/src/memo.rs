@@ -0,0 +1,122 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Deepest recursion reached through [`Memo::descend`]
+    pub max_depth: usize,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            100.0 * self.hit_rate()
+        )?;
+        if self.max_depth > 0 {
+            write!(f, ", max depth {}", self.max_depth)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Cache for recursive solvers, threaded through the recursion by `&mut`
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    depth: usize,
+    depth_limit: Option<usize>,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> From<HashMap<K, V>> for Memo<K, V> {
+    /// Seed the cache with known values
+    fn from(cache: HashMap<K, V>) -> Self {
+        Self {
+            cache,
+            depth: 0,
+            depth_limit: None,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        HashMap::new().into()
+    }
+
+    /// Limit how many nested [`Memo::descend`] calls are allowed
+    pub const fn with_depth_limit(mut self, limit: usize) -> Self {
+        self.depth_limit = Some(limit);
+        self
+    }
+
+    /// Look up a cached value, counting the lookup as a hit or miss.
+    ///
+    /// Accepts borrowed forms of the key, e.g. `&str` for `String` keys.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let res = self.cache.get(key);
+        if res.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        res
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// Run `f` one level deeper in the recursion, or return `None` without
+    /// running it if that would exceed the depth limit
+    pub fn descend<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        if self.depth_limit.is_some_and(|limit| self.depth >= limit) {
+            return None;
+        }
+
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+        let res = f(self);
+        self.depth -= 1;
+
+        Some(res)
+    }
+
+    pub const fn stats(&self) -> Stats {
+        self.stats
+    }
+}
@@ -0,0 +1,15 @@
+//! nom parsers shared by solvers
+
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize},
+    sequence::preceded,
+    IResult,
+};
+
+/// A decimal number, which may be negative if `T` is signed
+pub fn num<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(preceded(opt(char('-')), digit1)), str::parse)(input)
+}
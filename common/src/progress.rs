@@ -0,0 +1,90 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Tells solvers to give up once the `--timeout` has passed
+#[derive(Clone, Copy, Debug)]
+pub struct CancelToken {
+    deadline: Option<(Instant, Duration)>,
+}
+
+/// The error returned by [`CancelToken::check`] once the deadline has passed
+#[derive(Debug)]
+pub struct Cancelled {
+    timeout: Duration,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Timed out after {:?}", self.timeout)
+    }
+}
+
+impl Error for Cancelled {}
+
+impl CancelToken {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            deadline: timeout.map(|timeout| (Instant::now() + timeout, timeout)),
+        }
+    }
+
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => Err(Cancelled { timeout }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Progress of a long-running search, drawn on stderr when it is a terminal.
+///
+/// Every [`inc`](Self::inc) also polls the cancel token, so searches which
+/// report progress stop cleanly on timeout, saying how far they got.
+pub struct Progress {
+    bar: ProgressBar,
+    what: &'static str,
+    cancel: CancelToken,
+}
+
+impl Progress {
+    /// Track a search over `len` items, or an unknown number if `None`
+    pub fn new(what: &'static str, len: Option<u64>, cancel: CancelToken) -> Self {
+        let bar = match len {
+            Some(len) => ProgressBar::new(len).with_style(
+                ProgressStyle::with_template("{msg} [{elapsed}] {wide_bar} {pos}/{len}")
+                    .expect("Valid template"),
+            ),
+            None => ProgressBar::new_spinner().with_style(
+                ProgressStyle::with_template("{spinner} {msg} [{elapsed}] {pos}")
+                    .expect("Valid template"),
+            ),
+        };
+        Self {
+            bar: bar.with_message(what),
+            what,
+            cancel,
+        }
+    }
+
+    pub fn inc(&self, n: u64) -> Result<()> {
+        self.bar.inc(n);
+        self.cancel
+            .check()
+            .with_context(|| match self.bar.length() {
+                Some(len) => format!("Stopped at {}/{len} {}", self.bar.position(), self.what),
+                None => format!("Stopped after {} {}", self.bar.position(), self.what),
+            })
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Flushes the Chrome trace file, if any, when dropped
+pub struct Guard {
+    _chrome: Option<FlushGuard>,
+}
+
+/// Log to stderr filtered by `RUST_LOG`, and optionally record a Chrome trace.
+///
+/// Without `RUST_LOG`, only warnings are logged, or phase spans and summary
+/// counters (`info`) when tracing to a file.
+pub fn init(trace_file: Option<&Path>) -> Result<Guard> {
+    let default_level = if trace_file.is_some() { "info" } else { "warn" };
+    let filter =
+        EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(default_level))?;
+    let fmt = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+
+    let (chrome, guard) = match trace_file {
+        Some(path) => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt)
+        .with(chrome)
+        .try_init()
+        .map_err(|e| anyhow!("Failed to set up tracing: {e}"))?;
+
+    Ok(Guard { _chrome: guard })
+}